@@ -0,0 +1,45 @@
+//! Procedural macros for the [four-cc](https://docs.rs/four-cc) crate.
+//!
+//! Rather than depending on this crate directly, enable the `macros` feature of `four-cc`, which
+//! re-exports the macros defined here.
+
+#![forbid(unsafe_code)]
+#![deny(rust_2018_idioms, future_incompatible, missing_docs)]
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Lit};
+
+/// Creates a `FourCC` from a four-byte string or byte-string literal.
+///
+/// The expansion is a plain `FourCC([..])` expression, so it may be used in `const` items and
+/// directly in `match` patterns.  See the `four-cc` crate for examples.
+#[proc_macro]
+pub fn fourcc(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as Lit);
+    let bytes = match &lit {
+        Lit::Str(s) => s.value().into_bytes(),
+        Lit::ByteStr(s) => s.value(),
+        _ => {
+            return syn::Error::new(
+                lit.span(),
+                "fourcc! expects a string literal like \"moov\" or a byte string literal like b\"moov\"",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+    if bytes.len() != 4 {
+        return syn::Error::new(
+            lit.span(),
+            format!(
+                "fourcc! literal must be exactly 4 bytes long, but this one is {} bytes",
+                bytes.len()
+            ),
+        )
+        .to_compile_error()
+        .into();
+    }
+    let (a, b, c, d) = (bytes[0], bytes[1], bytes[2], bytes[3]);
+    quote!(::four_cc::FourCC([#a, #b, #c, #d])).into()
+}
@@ -0,0 +1,165 @@
+//! Extension traits for reading and writing codes with the [bytes](https://docs.rs/bytes) crate's
+//! [`Buf`] and [`BufMut`].
+//!
+//! A code is read and written as exactly its four bytes, in the same big-endian order as
+//! [`Buf::get_u32()`] and [`BufMut::put_u32()`].
+//!
+//! ```rust
+//! use bytes::{Buf, BufMut};
+//! use four_cc::{bytes::{FourCCBufExt, FourCCBufMutExt}, FourCC};
+//!
+//! let mut data = vec![];
+//! data.put_u32(8);
+//! data.put_fourcc(FourCC(*b"free"));
+//!
+//! let mut buf = &data[..];
+//! assert_eq!(8, buf.get_u32());
+//! assert_eq!(FourCC(*b"free"), buf.get_fourcc());
+//! assert!(buf.try_get_fourcc().is_err());
+//! ```
+//!
+//! _Requires the `bytes` feature._
+
+use crate::FourCC;
+use bytes::{Buf, BufMut, TryGetError};
+
+/// Extension methods for reading codes from any [`Buf`].
+pub trait FourCCBufExt: Buf {
+    /// Reads a code from the next four bytes, advancing the position by four.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than four bytes remain, like [`Buf::get_u32()`].
+    fn get_fourcc(&mut self) -> FourCC {
+        FourCC::from_u32(self.get_u32())
+    }
+
+    /// Reads a code from the next four bytes, advancing the position by four.
+    ///
+    /// Fails without advancing if fewer than four bytes remain, like [`Buf::try_get_u32()`].
+    /// Use `.ok()` on the result where the number of missing bytes isn't needed.
+    ///
+    /// ```rust
+    /// use four_cc::{bytes::FourCCBufExt, FourCC};
+    ///
+    /// let mut buf = &b"mdatmo"[..];
+    /// assert_eq!(Some(FourCC(*b"mdat")), buf.try_get_fourcc().ok());
+    /// assert_eq!(None, buf.try_get_fourcc().ok());
+    /// ```
+    fn try_get_fourcc(&mut self) -> Result<FourCC, TryGetError> {
+        self.try_get_u32().map(FourCC::from_u32)
+    }
+}
+
+impl<B: Buf + ?Sized> FourCCBufExt for B {}
+
+/// Extension methods for writing codes to any [`BufMut`].
+pub trait FourCCBufMutExt: BufMut {
+    /// Writes the four bytes of `code`, advancing the position by four.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not enough remaining capacity, like [`BufMut::put_u32()`].
+    fn put_fourcc(&mut self, code: FourCC) {
+        self.put_slice(&code.0)
+    }
+}
+
+impl<B: BufMut + ?Sized> FourCCBufMutExt for B {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get() {
+        let mut buf = &b"moov\0\0\0\0\xa9namrest"[..];
+        assert_eq!(FourCC(*b"moov"), buf.get_fourcc());
+        assert_eq!(Ok(FourCC::ZERO), buf.try_get_fourcc());
+        assert_eq!(FourCC(*b"\xa9nam"), buf.get_fourcc());
+        assert_eq!(b"rest", buf);
+    }
+
+    #[test]
+    fn chained() {
+        // every way of splitting the code across two segments
+        for split in 0..=4 {
+            let (first, second) = b"moov!".split_at(split);
+            let mut buf = first.chain(second);
+            assert_eq!(FourCC(*b"moov"), buf.get_fourcc());
+            assert_eq!(1, buf.remaining());
+
+            let mut buf = first.chain(second);
+            assert_eq!(Ok(FourCC(*b"moov")), buf.try_get_fourcc());
+            assert_eq!(b'!', buf.get_u8());
+        }
+        let mut buf = (&b"m"[..]).chain(&b"o"[..]).chain(&b"ov"[..]);
+        assert_eq!(FourCC(*b"moov"), buf.get_fourcc());
+    }
+
+    #[test]
+    fn too_short() {
+        let mut buf = (&b"mo"[..]).chain(&b"o"[..]);
+        assert_eq!(
+            Err(TryGetError {
+                requested: 4,
+                available: 3
+            }),
+            buf.try_get_fourcc()
+        );
+        // nothing was consumed
+        assert_eq!(3, buf.remaining());
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_too_short() {
+        let mut buf = &b"moo"[..];
+        buf.get_fourcc();
+    }
+
+    #[test]
+    fn put() {
+        let mut data = vec![];
+        data.put_fourcc(FourCC(*b"moov"));
+        data.put_fourcc(FourCC(*b"\xa9nam"));
+        assert_eq!(b"moov\xa9nam", &data[..]);
+
+        // writes spanning the segments of a chain
+        let mut first = [0u8; 3];
+        let mut second = [0u8; 3];
+        let mut buf = (&mut first[..]).chain_mut(&mut second[..]);
+        buf.put_fourcc(FourCC(*b"moov"));
+        assert_eq!(2, buf.remaining_mut());
+        assert_eq!(b"moo", &first);
+        assert_eq!(b"v\0\0", &second);
+    }
+
+    #[test]
+    fn same_as_u32() {
+        let data = b"\0\0\0\x01\xff\xfe\xfd\xfcmoov";
+        let mut codes = &data[..];
+        let mut ints = &data[..];
+        while codes.has_remaining() {
+            assert_eq!(FourCC::from(ints.get_u32()), codes.get_fourcc());
+        }
+
+        let mut from_codes = vec![];
+        let mut from_ints = vec![];
+        for code in [FourCC(*b"moov"), FourCC::MAX, FourCC::from_u32(1)] {
+            from_codes.put_fourcc(code);
+            from_ints.put_u32(code.into());
+        }
+        assert_eq!(from_ints, from_codes);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut data = bytes::BytesMut::new();
+        data.put_fourcc(FourCC(*b"ftyp"));
+        data.put_u32(FourCC(*b"isom").to_u32());
+        let mut data = data.freeze();
+        assert_eq!(FourCC(*b"ftyp"), FourCC::from_u32(data.get_u32()));
+        assert_eq!(FourCC(*b"isom"), data.get_fourcc());
+    }
+}
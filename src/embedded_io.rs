@@ -0,0 +1,208 @@
+//! Reading and writing codes with [embedded-io](https://docs.rs/embedded-io), and (with the
+//! `embedded-io-async` feature) [embedded-io-async](https://docs.rs/embedded-io-async).
+
+use crate::FourCC;
+use embedded_io::ReadExactError;
+
+/// _Requires the `embedded-io` feature._
+impl FourCC {
+    /// Reads a code from the next four bytes of `r`, repeating reads until all four bytes arrive.
+    ///
+    /// Fails with [`ReadExactError::UnexpectedEof`] if the reader reaches the end of its input
+    /// first.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let mut r = &b"RIFF\x04\0\0\0"[..];
+    /// assert_eq!(Ok(FourCC(*b"RIFF")), FourCC::read_from_embedded(&mut r));
+    /// ```
+    pub fn read_from_embedded<R: embedded_io::Read + ?Sized>(
+        r: &mut R,
+    ) -> Result<FourCC, ReadExactError<R::Error>> {
+        let mut buf = [0u8; 4];
+        r.read_exact(&mut buf)?;
+        Ok(FourCC(buf))
+    }
+
+    /// Writes the four bytes of this code to `w`, repeating writes until all four bytes are
+    /// accepted.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let mut buf = [0u8; 8];
+    /// let mut w = &mut buf[..];
+    /// FourCC(*b"RIFF").write_to_embedded(&mut w).unwrap();
+    /// assert_eq!(b"RIFF\0\0\0\0", &buf);
+    /// ```
+    pub fn write_to_embedded<W: embedded_io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), W::Error> {
+        w.write_all(&self.0)
+    }
+
+    /// Like [`FourCC::read_from_embedded()`], but for an asynchronous reader.
+    ///
+    /// _Requires the `embedded-io-async` feature._
+    #[cfg(feature = "embedded-io-async")]
+    pub async fn read_from_embedded_async<R: embedded_io_async::Read + ?Sized>(
+        r: &mut R,
+    ) -> Result<FourCC, ReadExactError<R::Error>> {
+        let mut buf = [0u8; 4];
+        r.read_exact(&mut buf).await?;
+        Ok(FourCC(buf))
+    }
+
+    /// Like [`FourCC::write_to_embedded()`], but for an asynchronous writer.
+    ///
+    /// _Requires the `embedded-io-async` feature._
+    #[cfg(feature = "embedded-io-async")]
+    pub async fn write_to_embedded_async<W: embedded_io_async::Write + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), W::Error> {
+        w.write_all(&self.0).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_io::{ErrorKind, ErrorType};
+
+    /// Returns at most one byte from each call to `read()`, failing once `fail_at` bytes have been
+    /// read
+    struct Uart<'a> {
+        data: &'a [u8],
+        read: usize,
+        fail_at: usize,
+    }
+
+    impl<'a> Uart<'a> {
+        fn new(data: &'a [u8]) -> Self {
+            Uart {
+                data,
+                read: 0,
+                fail_at: usize::MAX,
+            }
+        }
+    }
+
+    impl ErrorType for Uart<'_> {
+        type Error = ErrorKind;
+    }
+
+    impl embedded_io::Read for Uart<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+            if self.read == self.fail_at {
+                return Err(ErrorKind::Other);
+            }
+            let n = self.data.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            self.read += n;
+            Ok(n)
+        }
+    }
+
+    #[cfg(feature = "embedded-io-async")]
+    impl embedded_io_async::Read for Uart<'_> {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+            embedded_io::Read::read(self, buf)
+        }
+    }
+
+    /// Accepts at most one byte from each call to `write()`
+    struct Trickle<'a>(&'a mut [u8]);
+
+    impl ErrorType for Trickle<'_> {
+        type Error = ErrorKind;
+    }
+
+    impl embedded_io::Write for Trickle<'_> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, ErrorKind> {
+            let n = buf.len().min(1);
+            embedded_io::Write::write(&mut self.0, &buf[..n]).map_err(|_| ErrorKind::WriteZero)
+        }
+
+        fn flush(&mut self) -> Result<(), ErrorKind> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "embedded-io-async")]
+    impl embedded_io_async::Write for Trickle<'_> {
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, ErrorKind> {
+            embedded_io::Write::write(self, buf)
+        }
+    }
+
+    #[test]
+    fn read() {
+        let mut r = Uart::new(b"RIFFWAVEfm");
+        assert_eq!(Ok(FourCC(*b"RIFF")), FourCC::read_from_embedded(&mut r));
+        assert_eq!(Ok(FourCC(*b"WAVE")), FourCC::read_from_embedded(&mut r));
+        assert_eq!(
+            Err(ReadExactError::UnexpectedEof),
+            FourCC::read_from_embedded(&mut r)
+        );
+    }
+
+    #[test]
+    fn read_error() {
+        let mut r = Uart::new(b"RIFFWAVE");
+        r.fail_at = 6;
+        assert_eq!(Ok(FourCC(*b"RIFF")), FourCC::read_from_embedded(&mut r));
+        assert_eq!(
+            Err(ReadExactError::Other(ErrorKind::Other)),
+            FourCC::read_from_embedded(&mut r)
+        );
+    }
+
+    #[test]
+    fn write() {
+        let mut buf = [0u8; 6];
+        let mut w = Trickle(&mut buf);
+        FourCC(*b"RIFF").write_to_embedded(&mut w).unwrap();
+        assert_eq!(
+            Err(ErrorKind::WriteZero),
+            FourCC(*b"WAVE").write_to_embedded(&mut w)
+        );
+        assert_eq!(b"RIFFWA", &buf);
+    }
+
+    #[cfg(feature = "embedded-io-async")]
+    #[test]
+    fn read_write_async() {
+        futures::executor::block_on(async {
+            let mut r = Uart::new(b"RIFFWAVEfm");
+            assert_eq!(
+                Ok(FourCC(*b"RIFF")),
+                FourCC::read_from_embedded_async(&mut r).await
+            );
+            assert_eq!(
+                Ok(FourCC(*b"WAVE")),
+                FourCC::read_from_embedded_async(&mut r).await
+            );
+            assert_eq!(
+                Err(ReadExactError::UnexpectedEof),
+                FourCC::read_from_embedded_async(&mut r).await
+            );
+
+            let mut r = Uart::new(b"RIFF");
+            r.fail_at = 2;
+            assert_eq!(
+                Err(ReadExactError::Other(ErrorKind::Other)),
+                FourCC::read_from_embedded_async(&mut r).await
+            );
+
+            let mut buf = [0u8; 4];
+            let mut w = Trickle(&mut buf);
+            FourCC(*b"RIFF")
+                .write_to_embedded_async(&mut w)
+                .await
+                .unwrap();
+            assert_eq!(b"RIFF", &buf);
+        });
+    }
+}
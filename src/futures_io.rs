@@ -0,0 +1,188 @@
+//! Extension traits for reading and writing codes with the [futures-io](https://docs.rs/futures-io)
+//! `AsyncRead` and `AsyncWrite` traits, as used by async-std, smol and others.
+//!
+//! These mirror the extension traits of the `tokio` feature, but need no runtime or executor.
+//!
+//! ```rust
+//! use four_cc::{futures_io::{AsyncReadFourCCExt, AsyncWriteFourCCExt}, FourCC};
+//! use futures::io::Cursor;
+//!
+//! # futures::executor::block_on(async {
+//! let mut data = Cursor::new(vec![]);
+//! data.write_fourcc(FourCC(*b"moov")).await.unwrap();
+//! data.set_position(0);
+//! assert_eq!(FourCC(*b"moov"), data.read_fourcc().await.unwrap());
+//! # });
+//! ```
+//!
+//! _Requires the `futures-io` feature._
+
+use crate::FourCC;
+use futures_io::{AsyncRead, AsyncWrite};
+use std::future::{self, Future};
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Poll};
+
+/// Extension methods for reading codes from any futures-io [`AsyncRead`] implementation.
+pub trait AsyncReadFourCCExt: AsyncRead {
+    /// Reads a code from the next four bytes, repeating reads until all four bytes arrive.
+    ///
+    /// Fails with [`io::ErrorKind::UnexpectedEof`] if fewer than four bytes are available.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is not cancellation safe.  If the returned future is dropped before
+    /// completing, some of the code's bytes may already have been consumed from the reader, and
+    /// will be lost.
+    fn read_fourcc(&mut self) -> impl Future<Output = io::Result<FourCC>> + '_
+    where
+        Self: Unpin,
+    {
+        let mut buf = [0u8; 4];
+        let mut len = 0;
+        future::poll_fn(move |cx| {
+            while len < buf.len() {
+                match ready!(Pin::new(&mut *self).poll_read(cx, &mut buf[len..]))? {
+                    0 => return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into())),
+                    n => len += n,
+                }
+            }
+            Poll::Ready(Ok(FourCC(buf)))
+        })
+    }
+}
+
+impl<R: AsyncRead + ?Sized> AsyncReadFourCCExt for R {}
+
+/// Extension methods for writing codes to any futures-io [`AsyncWrite`] implementation.
+pub trait AsyncWriteFourCCExt: AsyncWrite {
+    /// Writes the four bytes of `code`, repeating writes until all four bytes are accepted.
+    ///
+    /// Fails with [`io::ErrorKind::WriteZero`] if the writer stops accepting bytes.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is not cancellation safe.  If the returned future is dropped before
+    /// completing, only some of the code's bytes may have been written.
+    fn write_fourcc(&mut self, code: FourCC) -> impl Future<Output = io::Result<()>> + '_
+    where
+        Self: Unpin,
+    {
+        let mut len = 0;
+        future::poll_fn(move |cx| {
+            while len < code.0.len() {
+                match ready!(Pin::new(&mut *self).poll_write(cx, &code.0[len..]))? {
+                    0 => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                    n => len += n,
+                }
+            }
+            Poll::Ready(Ok(()))
+        })
+    }
+}
+
+impl<W: AsyncWrite + ?Sized> AsyncWriteFourCCExt for W {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::io::Cursor;
+    use std::task::Context;
+
+    /// Returns at most one byte from each poll, and is pending before each byte
+    struct Trickle<'a> {
+        data: &'a [u8],
+        pending: bool,
+    }
+
+    impl AsyncRead for Trickle<'_> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            self.pending = !self.pending;
+            if self.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            let n = self.data.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Poll::Ready(Ok(n))
+        }
+    }
+
+    /// Accepts at most `capacity` bytes, one per poll
+    struct Limited {
+        data: Vec<u8>,
+        capacity: usize,
+    }
+
+    impl AsyncWrite for Limited {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            if self.data.len() == self.capacity {
+                return Poll::Ready(Ok(0));
+            }
+            self.data.extend(buf.first());
+            Poll::Ready(Ok(buf.len().min(1)))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn cursor() {
+        block_on(async {
+            let mut cursor = Cursor::new(vec![]);
+            cursor.write_fourcc(FourCC(*b"moov")).await.unwrap();
+            cursor.write_fourcc(FourCC(*b"\xa9nam")).await.unwrap();
+            assert_eq!(b"moov\xa9nam", &cursor.get_ref()[..]);
+            cursor.set_position(0);
+            assert_eq!(FourCC(*b"moov"), cursor.read_fourcc().await.unwrap());
+            assert_eq!(FourCC(*b"\xa9nam"), cursor.read_fourcc().await.unwrap());
+            let err = cursor.read_fourcc().await.unwrap_err();
+            assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+        });
+    }
+
+    #[test]
+    fn short_reads() {
+        block_on(async {
+            let mut r = Trickle {
+                data: b"moovtrakmd",
+                pending: false,
+            };
+            assert_eq!(FourCC(*b"moov"), r.read_fourcc().await.unwrap());
+            assert_eq!(FourCC(*b"trak"), r.read_fourcc().await.unwrap());
+            let err = r.read_fourcc().await.unwrap_err();
+            assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+        });
+    }
+
+    #[test]
+    fn short_writes() {
+        block_on(async {
+            let mut w = Limited {
+                data: vec![],
+                capacity: 6,
+            };
+            w.write_fourcc(FourCC(*b"moov")).await.unwrap();
+            let err = w.write_fourcc(FourCC(*b"trak")).await.unwrap_err();
+            assert_eq!(io::ErrorKind::WriteZero, err.kind());
+            assert_eq!(b"moovtr", &w.data[..]);
+        });
+    }
+}
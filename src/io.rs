@@ -0,0 +1,323 @@
+//! Helpers for reading and writing codes with [`std::io`], for use without serde.
+//!
+//! A code is always encoded as exactly its four bytes, with no length prefix or other framing,
+//! which matches the encoding produced by the serde implementation with `bincode`.
+//!
+//! ```rust
+//! use four_cc::{io::{decode_fourcc, encode_fourcc}, FourCC};
+//!
+//! let mut data = vec![];
+//! encode_fourcc(FourCC(*b"ftyp"), &mut data).unwrap();
+//! assert_eq!(b"ftyp", &data[..]);
+//! assert_eq!(FourCC(*b"ftyp"), decode_fourcc(&mut &data[..]).unwrap());
+//! ```
+//!
+//! The same operations are available as methods of any reader or writer, through the
+//! [`ReadFourCCExt`] and [`WriteFourCCExt`] extension traits,
+//!
+//! ```rust
+//! use four_cc::{io::{ReadFourCCExt, WriteFourCCExt}, FourCC};
+//! use std::io::Cursor;
+//!
+//! let mut data = Cursor::new(vec![]);
+//! data.write_fourcc(FourCC(*b"moov")).unwrap();
+//! data.set_position(0);
+//! assert_eq!(FourCC(*b"moov"), data.read_fourcc().unwrap());
+//! ```
+//!
+//! _Requires the `std` feature._
+
+use crate::FourCC;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+/// Writes the four bytes of `code` to `w`.
+pub fn encode_fourcc<W: Write + ?Sized>(code: FourCC, w: &mut W) -> io::Result<()> {
+    w.write_all(&code.0)
+}
+
+/// Reads a code from the next four bytes of `r`.
+///
+/// Fails with [`io::ErrorKind::UnexpectedEof`] if fewer than four bytes are available.
+pub fn decode_fourcc<R: Read + ?Sized>(r: &mut R) -> io::Result<FourCC> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(FourCC(buf))
+}
+
+/// _Requires the `std` feature._
+impl FourCC {
+    /// Reads a code from the next four bytes of `r`, in the same way as [`decode_fourcc()`].
+    ///
+    /// Exactly four bytes are consumed on success.  Fails with [`io::ErrorKind::UnexpectedEof`]
+    /// if fewer than four bytes are available.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let mut r = &b"ftypisom"[..];
+    /// assert_eq!(FourCC(*b"ftyp"), FourCC::read_io(&mut r).unwrap());
+    /// assert_eq!(b"isom", r);
+    /// ```
+    pub fn read_io<R: Read + ?Sized>(r: &mut R) -> io::Result<FourCC> {
+        decode_fourcc(r)
+    }
+
+    /// Writes the four bytes of this code to `w`, in the same way as [`encode_fourcc()`].
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let mut data = vec![];
+    /// FourCC(*b"ftyp").write_io(&mut data).unwrap();
+    /// assert_eq!(b"ftyp", &data[..]);
+    /// ```
+    pub fn write_io<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        encode_fourcc(*self, w)
+    }
+
+    /// Reads a code from the next four bytes of `r`, and then seeks back to where it started, for
+    /// sniffing the type of some data before choosing how to parse it.
+    ///
+    /// No bytes are consumed, whether or not this succeeds (unless seeking back itself fails).
+    /// Fails with [`io::ErrorKind::UnexpectedEof`] if fewer than four bytes are available.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// use std::io::Cursor;
+    ///
+    /// let mut r = Cursor::new(b"RIFF\x04\0\0\0WAVE");
+    /// assert_eq!(FourCC(*b"RIFF"), FourCC::peek_io(&mut r).unwrap());
+    /// assert_eq!(0, r.position());
+    /// ```
+    pub fn peek_io<R: Read + Seek + ?Sized>(r: &mut R) -> io::Result<FourCC> {
+        let mut buf = [0u8; 4];
+        let mut len = 0;
+        let result = loop {
+            if len == buf.len() {
+                break Ok(FourCC(buf));
+            }
+            match r.read(&mut buf[len..]) {
+                Ok(0) => break Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => len += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => break Err(e),
+            }
+        };
+        r.seek(SeekFrom::Current(-(len as i64)))?;
+        result
+    }
+}
+
+/// Extension methods for reading codes from any [`Read`] implementation.
+pub trait ReadFourCCExt: Read {
+    /// Reads a code from the next four bytes, in the same way as [`decode_fourcc()`].
+    ///
+    /// Fails with [`io::ErrorKind::UnexpectedEof`] if fewer than four bytes are available.
+    fn read_fourcc(&mut self) -> io::Result<FourCC> {
+        decode_fourcc(self)
+    }
+}
+
+impl<R: Read + ?Sized> ReadFourCCExt for R {}
+
+/// Extension methods for writing codes to any [`Write`] implementation.
+pub trait WriteFourCCExt: Write {
+    /// Writes the four bytes of `code`, in the same way as [`encode_fourcc()`].
+    fn write_fourcc(&mut self, code: FourCC) -> io::Result<()> {
+        encode_fourcc(code, self)
+    }
+}
+
+impl<W: Write + ?Sized> WriteFourCCExt for W {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn round_trip() {
+        let codes = [FourCC(*b"moov"), FourCC(*b"\0\0\0\0"), FourCC(*b"\xa9nam")];
+        let mut data = vec![];
+        for code in codes {
+            encode_fourcc(code, &mut data).unwrap();
+        }
+        assert_eq!(b"moov\0\0\0\0\xa9nam", &data[..]);
+        let mut r = &data[..];
+        for code in codes {
+            assert_eq!(code, decode_fourcc(&mut r).unwrap());
+        }
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn truncated() {
+        let err = decode_fourcc(&mut &b"moo"[..]).unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    /// Returns at most one byte from each call to `read()`, and is interrupted before each byte
+    struct Trickle<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            (&mut self.data).take(1).read(buf)
+        }
+    }
+
+    /// Accepts at most `capacity` bytes, one per call, and then fails
+    struct Limited {
+        data: Vec<u8>,
+        capacity: usize,
+    }
+
+    impl Write for Limited {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.data.len() == self.capacity {
+                return Err(io::Error::other("full"));
+            }
+            self.data.extend(buf.first());
+            Ok(buf.len().min(1))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn ext_cursor() {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_fourcc(FourCC(*b"moov")).unwrap();
+        cursor.write_fourcc(FourCC(*b"\xa9nam")).unwrap();
+        assert_eq!(b"moov\xa9nam", &cursor.get_ref()[..]);
+        cursor.set_position(0);
+        assert_eq!(FourCC(*b"moov"), cursor.read_fourcc().unwrap());
+        assert_eq!(FourCC(*b"\xa9nam"), cursor.read_fourcc().unwrap());
+        let err = cursor.read_fourcc().unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+
+        let mut cursor = Cursor::new(&b"moo"[..]);
+        let err = cursor.read_fourcc().unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    #[test]
+    fn ext_short_reads() {
+        let mut r = Trickle {
+            data: b"moovfre",
+            interrupt: false,
+        };
+        assert_eq!(FourCC(*b"moov"), r.read_fourcc().unwrap());
+        let err = r.read_fourcc().unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    #[test]
+    fn ext_failing_writer() {
+        let mut w = Limited {
+            data: vec![],
+            capacity: 6,
+        };
+        w.write_fourcc(FourCC(*b"moov")).unwrap();
+        let err = w.write_fourcc(FourCC(*b"trak")).unwrap_err();
+        assert_eq!(io::ErrorKind::Other, err.kind());
+        assert_eq!(b"moovtr", &w.data[..]);
+        // also usable through a trait object
+        let w: &mut dyn Write = &mut w;
+        assert!(w.write_fourcc(FourCC(*b"mdat")).is_err());
+    }
+
+    #[test]
+    fn read_io_write_io() {
+        let mut data = vec![];
+        FourCC(*b"moov").write_io(&mut data).unwrap();
+        FourCC(*b"mvhd").write_io(&mut data).unwrap();
+        assert_eq!(b"moovmvhd", &data[..]);
+
+        let mut cursor = Cursor::new(&data[..]);
+        assert_eq!(FourCC(*b"moov"), FourCC::read_io(&mut cursor).unwrap());
+        assert_eq!(4, cursor.position());
+
+        let mut r = Trickle {
+            data: &data,
+            interrupt: false,
+        };
+        assert_eq!(FourCC(*b"moov"), FourCC::read_io(&mut r).unwrap());
+        assert_eq!(FourCC(*b"mvhd"), FourCC::read_io(&mut r).unwrap());
+
+        let err = FourCC::read_io(&mut &b"mo"[..]).unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    #[test]
+    fn peek_io() {
+        let mut cursor = Cursor::new(&b"moovmvhdmo"[..]);
+        assert_eq!(FourCC(*b"moov"), FourCC::peek_io(&mut cursor).unwrap());
+        assert_eq!(FourCC(*b"moov"), FourCC::peek_io(&mut cursor).unwrap());
+        assert_eq!(0, cursor.position());
+        assert_eq!(FourCC(*b"moov"), cursor.read_fourcc().unwrap());
+        assert_eq!(FourCC(*b"mvhd"), FourCC::peek_io(&mut cursor).unwrap());
+        assert_eq!(4, cursor.position());
+        cursor.set_position(8);
+
+        // EOF after two bytes leaves the position unchanged
+        let err = FourCC::peek_io(&mut cursor).unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+        assert_eq!(8, cursor.position());
+        let err = FourCC::peek_io(&mut Cursor::new(&b""[..])).unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    #[test]
+    fn peek_io_short_reads() {
+        /// A seekable reader which returns at most one byte from each call to `read()`
+        struct SeekableTrickle<'a>(Trickle<'a>, Cursor<&'a [u8]>);
+
+        impl Read for SeekableTrickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let n = self.0.read(buf)?;
+                self.1.set_position(self.1.position() + n as u64);
+                Ok(n)
+            }
+        }
+
+        impl Seek for SeekableTrickle<'_> {
+            fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+                let pos = self.1.seek(pos)?;
+                self.0.data = &self.1.get_ref()[pos as usize..];
+                Ok(pos)
+            }
+        }
+
+        let data = &b"moovmo"[..];
+        let mut r = SeekableTrickle(
+            Trickle {
+                data,
+                interrupt: false,
+            },
+            Cursor::new(data),
+        );
+        assert_eq!(FourCC(*b"moov"), FourCC::peek_io(&mut r).unwrap());
+        assert_eq!(0, r.stream_position().unwrap());
+        r.seek(SeekFrom::Start(4)).unwrap();
+        let err = FourCC::peek_io(&mut r).unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+        assert_eq!(4, r.stream_position().unwrap());
+        assert_eq!(b"mo", r.0.data);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn matches_bincode() {
+        let code = FourCC(*b"\0mp4");
+        let mut data = vec![];
+        encode_fourcc(code, &mut data).unwrap();
+        assert_eq!(bincode1::serialize(&code).unwrap(), data);
+    }
+}
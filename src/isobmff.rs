@@ -0,0 +1,126 @@
+//! Constants for well-known box types from the ISO base media file format (ISO/IEC 14496-12),
+//! which is the basis of MP4 and shares its structure with QuickTime.
+//!
+//! These may be used directly in `match` patterns,
+//!
+//! ```rust
+//! use four_cc::{isobmff, FourCC};
+//!
+//! # let box_type = FourCC(*b"trak");
+//! match box_type {
+//!     isobmff::MOOV => println!("movie"),
+//!     isobmff::TRAK => println!("track"),
+//!     _ => println!("other box"),
+//! }
+//! ```
+//!
+//! _Requires the `isobmff` feature._
+
+use crate::FourCC;
+
+/// File type and compatibility.
+pub const FTYP: FourCC = FourCC(*b"ftyp");
+/// Segment type.
+pub const STYP: FourCC = FourCC(*b"styp");
+/// Container for all the metadata.
+pub const MOOV: FourCC = FourCC(*b"moov");
+/// Movie header.
+pub const MVHD: FourCC = FourCC(*b"mvhd");
+/// Movie extends, signalling that movie fragments may be present.
+pub const MVEX: FourCC = FourCC(*b"mvex");
+/// Track extends, giving defaults for movie fragments.
+pub const TREX: FourCC = FourCC(*b"trex");
+/// Container for an individual track.
+pub const TRAK: FourCC = FourCC(*b"trak");
+/// Track header.
+pub const TKHD: FourCC = FourCC(*b"tkhd");
+/// Edit list container.
+pub const EDTS: FourCC = FourCC(*b"edts");
+/// Edit list.
+pub const ELST: FourCC = FourCC(*b"elst");
+/// Container for the media information in a track.
+pub const MDIA: FourCC = FourCC(*b"mdia");
+/// Media header.
+pub const MDHD: FourCC = FourCC(*b"mdhd");
+/// Handler, declaring the media type.
+pub const HDLR: FourCC = FourCC(*b"hdlr");
+/// Media information container.
+pub const MINF: FourCC = FourCC(*b"minf");
+/// Video media header.
+pub const VMHD: FourCC = FourCC(*b"vmhd");
+/// Sound media header.
+pub const SMHD: FourCC = FourCC(*b"smhd");
+/// Data information container.
+pub const DINF: FourCC = FourCC(*b"dinf");
+/// Data reference.
+pub const DREF: FourCC = FourCC(*b"dref");
+/// Sample table container.
+pub const STBL: FourCC = FourCC(*b"stbl");
+/// Sample descriptions.
+pub const STSD: FourCC = FourCC(*b"stsd");
+/// Decoding time to sample.
+pub const STTS: FourCC = FourCC(*b"stts");
+/// Composition time to sample.
+pub const CTTS: FourCC = FourCC(*b"ctts");
+/// Sample to chunk.
+pub const STSC: FourCC = FourCC(*b"stsc");
+/// Sample sizes.
+pub const STSZ: FourCC = FourCC(*b"stsz");
+/// Chunk offsets, 32-bit.
+pub const STCO: FourCC = FourCC(*b"stco");
+/// Chunk offsets, 64-bit.
+pub const CO64: FourCC = FourCC(*b"co64");
+/// Sync sample table.
+pub const STSS: FourCC = FourCC(*b"stss");
+/// Movie fragment.
+pub const MOOF: FourCC = FourCC(*b"moof");
+/// Movie fragment header.
+pub const MFHD: FourCC = FourCC(*b"mfhd");
+/// Track fragment.
+pub const TRAF: FourCC = FourCC(*b"traf");
+/// Track fragment header.
+pub const TFHD: FourCC = FourCC(*b"tfhd");
+/// Track fragment decode time.
+pub const TFDT: FourCC = FourCC(*b"tfdt");
+/// Track fragment run.
+pub const TRUN: FourCC = FourCC(*b"trun");
+/// Movie fragment random access.
+pub const MFRA: FourCC = FourCC(*b"mfra");
+/// Track fragment random access.
+pub const TFRA: FourCC = FourCC(*b"tfra");
+/// Movie fragment random access offset.
+pub const MFRO: FourCC = FourCC(*b"mfro");
+/// Segment index.
+pub const SIDX: FourCC = FourCC(*b"sidx");
+/// Event message.
+pub const EMSG: FourCC = FourCC(*b"emsg");
+/// Protection system specific header.
+pub const PSSH: FourCC = FourCC(*b"pssh");
+/// Media data.
+pub const MDAT: FourCC = FourCC(*b"mdat");
+/// Free space.
+pub const FREE: FourCC = FourCC(*b"free");
+/// Free space.
+pub const SKIP: FourCC = FourCC(*b"skip");
+/// User data.
+pub const UDTA: FourCC = FourCC(*b"udta");
+/// Metadata container.
+pub const META: FourCC = FourCC(*b"meta");
+/// Extended type, followed by a 16-byte UUID identifying the box.
+pub const UUID: FourCC = FourCC(*b"uuid");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values() {
+        assert_eq!(FourCC(*b"moov"), MOOV);
+        assert_eq!(FourCC(*b"moof"), MOOF);
+        assert_eq!(FourCC(*b"mdat"), MDAT);
+        assert_eq!(FourCC(*b"trun"), TRUN);
+        assert_eq!(FourCC(*b"ftyp"), FTYP);
+        assert_eq!(FourCC(*b"uuid"), UUID);
+        assert_eq!(FourCC(*b"co64"), CO64);
+    }
+}
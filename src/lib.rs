@@ -18,11 +18,16 @@
 //!
 //! ## From a slice
 //!
+//! Conversion from a slice is fallible, and only succeeds if the slice is exactly four bytes long,
+//!
 //! ```rust
 //! # use four_cc::FourCC;
 //! let data = b"moofftyp";
-//! let code = FourCC::from(&data[0..4]);  // would panic if fewer than 4 bytes
+//! let code = FourCC::try_from_slice(&data[0..4]).unwrap();
 //! assert_eq!(FourCC(*b"moof"), code);
+//! // truncated input produces an error rather than a panic
+//! let err = FourCC::try_from_slice(&data[0..3]).unwrap_err();
+//! assert_eq!(3, err.actual_len());
 //! ```
 //!
 //! ## From a u32
@@ -44,6 +49,10 @@
 //! ```rust
 //! # use four_cc::FourCC;
 //! const UUID: FourCC = FourCC(*b"uuid");
+//! // or, equivalently
+//! const MOOV: FourCC = FourCC::from_bytes(b"moov");
+//! // or, building the value from individual bytes
+//! const MP4A: FourCC = FourCC::new(b'm', b'p', b'4', b'a');
 //! ```
 //!
 //! ## Matching
@@ -63,6 +72,9 @@
 //! }
 //! ```
 //!
+//! Alternatively, with the `macros` feature enabled, the [`fourcc!`](macro.fourcc.html) macro can
+//! be used directly in match patterns.
+//!
 //! ## Invalid literal values
 //!
 //! If the literal has other than four bytes, compilation will fail
@@ -81,18 +93,20 @@
 //! # use std::fmt::Debug;
 //! let uuid = FourCC(*b"uuid");
 //! # assert_eq!("FourCC(uuid)", format!("{:?}", &uuid));
-//! println!("it's {:?}", uuid);  // produces: it's FourCC{uuid}
+//! println!("it's {:?}", uuid);  // produces: it's FourCC(uuid)
 //! ```
 //!
-//! Note that if the FourCC bytes are not able to be converted to UTF8, then a fallback
-//! representation will be used (as it would be surprising for `format!()` to panic).
+//! Note that bytes which are not printable ASCII are escaped, as by
+//! [`core::ascii::escape_default()`], rather than being written as-is (as it would be surprising
+//! for `format!()` to panic, or to produce control characters).  Quotes and backslashes are also
+//! escaped, exactly once.
 //!
 //! ```rust
 //! # use four_cc::FourCC;
 //! # use std::fmt::Debug;
 //! let uuid = FourCC(*b"u\xFFi\0");
 //! # assert_eq!("FourCC(u\\xffi\\x00)", format!("{:?}", &uuid));
-//! println!("it's {:?}", uuid);  // produces: it's FourCC{u\xffi\x00}
+//! println!("it's {:?}", uuid);  // produces: it's FourCC(u\xffi\x00)
 //! ```
 
 #![forbid(unsafe_code)]
@@ -100,70 +114,1089 @@
 #![cfg_attr(feature = "nightly", feature(const_trait_impl))]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(any(feature = "alloc", feature = "quickcheck", feature = "schemars1"))]
+extern crate alloc;
+
+use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::fmt;
-use core::fmt::Write;
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, IndexMut, Not,
+};
 use core::result::Result;
 use core::str::FromStr;
 
+/// Creates a [`FourCC`] from a four-byte string or byte-string literal.
+///
+/// Unlike `FourCC(*b"moov")`, the macro may be used directly as a `match` pattern,
+///
+/// ```rust
+/// # use four_cc::{fourcc, FourCC};
+/// # let code = FourCC(*b"trak");
+/// match code {
+///     fourcc!("moov") => println!("movie"),
+///     fourcc!(b"trak") => println!("track"),
+///     _ => println!("Other value"),
+/// }
+/// # assert_eq!(FourCC(*b"moov"), fourcc!("moov"));
+/// ```
+///
+/// Literals which are not exactly four bytes long are rejected at compile time,
+///
+/// ```compile_fail
+/// # use four_cc::fourcc;
+/// let bad_fourcc = fourcc!("moo");
+/// // -> fourcc! literal must be exactly 4 bytes long, but this one is 3 bytes
+/// ```
+///
+/// ```compile_fail
+/// # use four_cc::fourcc;
+/// let bad_fourcc = fourcc!(b"moovs");
+/// // -> fourcc! literal must be exactly 4 bytes long, but this one is 5 bytes
+/// ```
+///
+/// _Requires the `macros` feature._
+#[cfg(feature = "macros")]
+pub use four_cc_macros::fourcc;
+// allows the `::four_cc::FourCC` paths produced by `fourcc!` to resolve in this crate's tests
+#[cfg(all(test, feature = "macros"))]
+extern crate self as four_cc;
+
 /// A _four-character-code_ value.
 ///
 /// See the [module level documentation](index.html).
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "zerocopy", derive(zerocopy::FromBytes, zerocopy::AsBytes))]
-#[repr(C, packed)]
-pub struct FourCC(pub [u8; 4]);
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(zerocopy::FromZeroes, zerocopy::FromBytes, zerocopy::AsBytes)
+)]
+#[cfg_attr(
+    feature = "zerocopy08",
+    derive(
+        zerocopy08::FromBytes,
+        zerocopy08::IntoBytes,
+        zerocopy08::Immutable,
+        zerocopy08::KnownLayout,
+        zerocopy08::Unaligned
+    ),
+    zerocopy(crate = "zerocopy08")
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(
+        rkyv::Archive,
+        rkyv::Serialize,
+        rkyv::Deserialize,
+        rkyv::Portable,
+        rkyv::bytecheck::CheckBytes
+    ),
+    rkyv(as = FourCC),
+    bytecheck(crate = rkyv::bytecheck)
+)]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(all(feature = "borsh", feature = "std"), derive(borsh::BorshSchema))]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+#[repr(transparent)]
+pub struct FourCC(
+    /// The four bytes of the code.
+    ///
+    /// Reading this field directly is a legacy of earlier versions, which ties code to the
+    /// representation of `FourCC`.  Prefer [`FourCC::bytes()`] and [`FourCC::byte()`] instead.
+    pub [u8; 4],
+);
+
+// FourCC has exactly the layout of `[u8; 4]`
+const _: () = assert!(core::mem::size_of::<FourCC>() == 4);
+const _: () = assert!(core::mem::align_of::<FourCC>() == 1);
 impl FourCC {
-    const fn from_u32(self: Self) -> u32 {
-        ((self.0[0] as u32) << 24 & 0xff000000)
-            | ((self.0[1] as u32) << 16 & 0x00ff0000)
-            | ((self.0[2] as u32) << 8 & 0x0000ff00)
-            | ((self.0[3] as u32) & 0x000000ff)
+    /// The code with all four bytes zero, which some formats use to mean "no value".
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let codes = [FourCC::ZERO; 8];
+    /// assert!(codes.iter().all(FourCC::is_zero));
+    /// ```
+    pub const ZERO: FourCC = FourCC([0, 0, 0, 0]);
+
+    /// The smallest code, according to the `Ord` implementation (equal to [`FourCC::ZERO`]).
+    pub const MIN: FourCC = FourCC([0, 0, 0, 0]);
+
+    /// The largest code, according to the `Ord` implementation.
+    pub const MAX: FourCC = FourCC([0xff, 0xff, 0xff, 0xff]);
+
+    /// Creates a FourCC from its four individual bytes, given in order.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// const MOOV: FourCC = FourCC::new(b'm', b'o', b'o', b'v');
+    /// assert_eq!(FourCC(*b"moov"), MOOV);
+    /// ```
+    #[must_use]
+    pub const fn new(a: u8, b: u8, c: u8, d: u8) -> FourCC {
+        FourCC([a, b, c, d])
+    }
+
+    /// Creates a FourCC from a reference to a four-byte array, such as a byte-string literal.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// const UUID: FourCC = FourCC::from_bytes(b"uuid");
+    /// assert_eq!(FourCC(*b"uuid"), UUID);
+    /// ```
+    #[must_use]
+    pub const fn from_bytes(bytes: &[u8; 4]) -> FourCC {
+        FourCC(*bytes)
+    }
+
+    /// Creates a FourCC from a slice, failing unless the slice is exactly four bytes long.
+    ///
+    /// This is the fallible alternative to the `From<&[u8]>` implementation, which panics on short
+    /// input.  (A `TryFrom<&[u8]>` implementation can't be provided alongside `From<&[u8]>`, since
+    /// the standard library already derives an infallible `TryFrom` from every `From`.)
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let header = b"\0\0\0\x08free";
+    /// assert_eq!(Ok(FourCC(*b"free")), FourCC::try_from_slice(&header[4..]));
+    /// assert_eq!(2, FourCC::try_from_slice(&header[..2]).unwrap_err().actual_len());
+    /// ```
+    pub const fn try_from_slice(buf: &[u8]) -> Result<FourCC, FourCCFromSliceError> {
+        match buf {
+            &[a, b, c, d] => Ok(FourCC([a, b, c, d])),
+            _ => Err(FourCCFromSliceError { actual: buf.len() }),
+        }
+    }
+
+    /// Returns a reference to the four bytes of this code.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(b"moov", FourCC(*b"moov").as_bytes());
+    /// ```
+    pub const fn as_bytes(&self) -> &[u8; 4] {
+        &self.0
+    }
+
+    /// Returns a copy of the four bytes of this code.
+    ///
+    /// This is the recommended way to access the bytes, rather than the `.0` field.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let [a, b, c, d] = FourCC(*b"moov").bytes();
+    /// assert_eq!((b'm', b'o', b'o', b'v'), (a, b, c, d));
+    /// ```
+    pub const fn bytes(&self) -> [u8; 4] {
+        self.0
+    }
+
+    /// Returns the byte at index `i` of this code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than 4.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// const FIRST: u8 = FourCC(*b"moov").byte(0);
+    /// assert_eq!(b'm', FIRST);
+    /// ```
+    pub const fn byte(&self, i: usize) -> u8 {
+        self.0[i]
+    }
+
+    /// Returns a copy of the four bytes of this code.
+    ///
+    /// This is equivalent to the `From<FourCC> for [u8; 4]` implementation, but usable in `const`
+    /// contexts,
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// const BYTES: [u8; 4] = FourCC(*b"moov").to_array();
+    /// assert_eq!(*b"moov", BYTES);
+    /// ```
+    pub const fn to_array(self) -> [u8; 4] {
+        self.0
+    }
+
+    /// Returns the bytes of this code as a string slice, without copying, or an error if they are
+    /// not valid UTF-8.
+    ///
+    /// Unlike the `Display` implementation, no escaping is applied, so e.g. a trailing space or
+    /// control character is returned as-is.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(Ok("jp2 "), FourCC(*b"jp2 ").as_str());
+    /// assert!(FourCC(*b"u\xffi\0").as_str().is_err());
+    /// ```
+    pub fn as_str(&self) -> Result<&str, core::str::Utf8Error> {
+        core::str::from_utf8(&self.0)
+    }
+
+    /// Returns `true` if all four bytes are zero, i.e. this code is equal to [`FourCC::ZERO`].
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert!(FourCC(*b"\0\0\0\0").is_zero());
+    /// assert!(!FourCC(*b"\0\0\0 ").is_zero());
+    /// ```
+    pub const fn is_zero(&self) -> bool {
+        self.to_u32() == 0
+    }
+
+    /// Returns an iterator over the four bytes of this code.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert!(FourCC(*b"moov").iter().all(u8::is_ascii_lowercase));
+    /// ```
+    pub fn iter(&self) -> core::slice::Iter<'_, u8> {
+        self.0.iter()
+    }
+
+    /// Builds a code from an iterator which must yield exactly four bytes.
+    ///
+    /// This is used in place of a `FromIterator` implementation, which would have to either panic
+    /// or silently truncate given the wrong number of bytes.
+    ///
+    /// ```rust
+    /// # use four_cc::{FourCC, FourCCFromIterError};
+    /// let code = FourCC::try_from_iter(b"MOOV".iter().map(u8::to_ascii_lowercase));
+    /// assert_eq!(Ok(FourCC(*b"moov")), code);
+    /// assert_eq!(
+    ///     Err(FourCCFromIterError::TooShort { found: 3 }),
+    ///     FourCC::try_from_iter(b"moo".iter().copied())
+    /// );
+    /// assert_eq!(
+    ///     Err(FourCCFromIterError::TooLong),
+    ///     FourCC::try_from_iter(core::iter::repeat(b' '))
+    /// );
+    /// ```
+    pub fn try_from_iter<I: IntoIterator<Item = u8>>(
+        iter: I,
+    ) -> Result<FourCC, FourCCFromIterError> {
+        let mut iter = iter.into_iter();
+        let mut buf = [0u8; 4];
+        for (found, b) in buf.iter_mut().enumerate() {
+            *b = iter.next().ok_or(FourCCFromIterError::TooShort { found })?;
+        }
+        if iter.next().is_some() {
+            return Err(FourCCFromIterError::TooLong);
+        }
+        Ok(FourCC(buf))
+    }
+
+    /// Returns the bytes of this code, omitting any trailing spaces (`0x20`).
+    ///
+    /// Many codes shorter than four characters are padded with spaces, such as `jp2 `.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(b"jp2", FourCC(*b"jp2 ").trim_end());
+    /// ```
+    pub fn trim_end(&self) -> &[u8] {
+        let len = self.0.iter().rposition(|&b| b != b' ').map_or(0, |i| i + 1);
+        &self.0[..len]
+    }
+
+    /// Parses a code from one to four ASCII characters, padding shorter codes with trailing spaces
+    /// (`0x20`).  This is the reverse of [`FourCC::trim_end()`].
+    ///
+    /// ```rust
+    /// # use four_cc::{FourCC, FourCCParseErrorKind};
+    /// assert_eq!(Ok(FourCC(*b"id3 ")), FourCC::from_str_padded("id3"));
+    /// assert_eq!(Ok(FourCC(*b"moov")), FourCC::from_str_padded("moov"));
+    /// assert_eq!(
+    ///     Err(FourCCParseErrorKind::WrongLength { found: 5 }),
+    ///     FourCC::from_str_padded("moovs").map_err(|e| e.kind())
+    /// );
+    /// ```
+    pub fn from_str_padded(s: &str) -> Result<FourCC, FourCCParseError> {
+        if s.is_empty() || s.len() > 4 {
+            let kind = FourCCParseErrorKind::WrongLength { found: s.len() };
+            return Err(FourCCParseError::new(kind, s));
+        }
+        if let Some(position) = s.bytes().position(|b| !b.is_ascii()) {
+            let kind = FourCCParseErrorKind::NonAscii { position };
+            return Err(FourCCParseError::new(kind, s));
+        }
+        let mut buf = [b' '; 4];
+        buf[..s.len()].copy_from_slice(s.as_bytes());
+        Ok(FourCC(buf))
+    }
+
+    /// Returns `true` if all four bytes are printable ASCII characters (`0x20..=0x7E`, including
+    /// space), in which case the `Display` implementation will not need to escape anything.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert!(FourCC(*b"jp2 ").is_printable());
+    /// assert!(!FourCC(*b"\0uid").is_printable());
+    /// ```
+    #[doc(alias = "is_printable_ascii")]
+    pub const fn is_printable(&self) -> bool {
+        let mut i = 0;
+        while i < self.0.len() {
+            if self.0[i] < 0x20 || self.0[i] > 0x7e {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns `true` if all four bytes are ASCII (`0x00..=0x7F`), though possibly not printable.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert!(FourCC(*b"\0uid").is_ascii());
+    /// assert!(!FourCC(*b"\xa9nam").is_ascii());
+    /// ```
+    pub const fn is_ascii(&self) -> bool {
+        u32::from_ne_bytes(self.0) & 0x80808080 == 0
+    }
+
+    /// Returns `true` if all four bytes are ASCII letters or digits.
+    ///
+    /// This is stricter than [`FourCC::is_printable()`], rejecting e.g. space padding.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert!(FourCC(*b"avc1").is_ascii_alphanumeric());
+    /// assert!(!FourCC(*b"jp2 ").is_ascii_alphanumeric());
+    /// ```
+    #[doc(alias = "is_alphanumeric_ascii")]
+    pub const fn is_ascii_alphanumeric(&self) -> bool {
+        self.0[0].is_ascii_alphanumeric()
+            && self.0[1].is_ascii_alphanumeric()
+            && self.0[2].is_ascii_alphanumeric()
+            && self.0[3].is_ascii_alphanumeric()
+    }
+
+    /// Returns a copy of this code with any ASCII lowercase letters converted to uppercase.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(FourCC(*b"AVC1"), FourCC(*b"avc1").to_ascii_uppercase());
+    /// ```
+    #[must_use]
+    pub const fn to_ascii_uppercase(&self) -> FourCC {
+        FourCC([
+            self.0[0].to_ascii_uppercase(),
+            self.0[1].to_ascii_uppercase(),
+            self.0[2].to_ascii_uppercase(),
+            self.0[3].to_ascii_uppercase(),
+        ])
+    }
+
+    /// Returns a copy of this code with any ASCII uppercase letters converted to lowercase.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(FourCC(*b"avc1"), FourCC(*b"AVC1").to_ascii_lowercase());
+    /// ```
+    #[must_use]
+    pub const fn to_ascii_lowercase(&self) -> FourCC {
+        FourCC([
+            self.0[0].to_ascii_lowercase(),
+            self.0[1].to_ascii_lowercase(),
+            self.0[2].to_ascii_lowercase(),
+            self.0[3].to_ascii_lowercase(),
+        ])
+    }
+
+    /// Checks that two codes are equal, like `==`, but usable in `const` contexts.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// const MOOV: FourCC = FourCC(*b"moov");
+    /// const MOOF: FourCC = FourCC(*b"moof");
+    /// const _: () = assert!(!MOOV.const_eq(&MOOF));
+    /// ```
+    pub const fn const_eq(&self, other: &FourCC) -> bool {
+        self.to_u32() == other.to_u32()
+    }
+
+    /// Checks that two codes are equal, ignoring the case of any ASCII letters.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert!(FourCC(*b"avc1").eq_ignore_ascii_case(&FourCC(*b"AVC1")));
+    /// ```
+    pub const fn eq_ignore_ascii_case(&self, other: &FourCC) -> bool {
+        self.0[0].eq_ignore_ascii_case(&other.0[0])
+            && self.0[1].eq_ignore_ascii_case(&other.0[1])
+            && self.0[2].eq_ignore_ascii_case(&other.0[2])
+            && self.0[3].eq_ignore_ascii_case(&other.0[3])
+    }
+
+    /// Returns a value which displays the code as eight lowercase hex digits.
+    ///
+    /// Unlike the `Display` implementation of `FourCC` itself, this representation is unambiguous
+    /// for any byte values, which can be useful in diagnostics about malformed input.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let code = FourCC(*b"moov");
+    /// assert_eq!("6d6f6f76", format!("{}", code.hex()));
+    /// assert_eq!("0000ff0a", format!("{}", FourCC(*b"\0\0\xff\n").hex()));
+    /// ```
+    pub fn hex(&self) -> impl fmt::Display {
+        self.to_hex_string()
+    }
+
+    /// Returns the code as eight lowercase hex digits, without allocating.
+    ///
+    /// This is the inverse of [`FourCC::from_hex_str()`].
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let hex = FourCC(*b"moov").to_hex_string();
+    /// assert_eq!("6d6f6f76", &*hex);
+    /// ```
+    pub const fn to_hex_string(&self) -> HexString {
+        HexString(hex_digits(self, b"0123456789abcdef"))
+    }
+
+    /// Parses a code from exactly eight hex digits (in either case), as produced by
+    /// [`FourCC::to_hex_string()`].
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(Ok(FourCC(*b"moov")), FourCC::from_hex_str("6D6F6F76"));
+    /// assert!(FourCC::from_hex_str("6d6f6f7").is_err());
+    /// ```
+    #[doc(alias = "from_hex")]
+    pub fn from_hex_str(s: &str) -> Result<FourCC, FourCCParseError> {
+        parse_hex(s, 0).map_err(|kind| FourCCParseError::new(kind, s))
+    }
+
+    /// Like [`FourCC::from_hex_str()`], but additionally accepts a `0x` or `0X` prefix.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(Ok(FourCC(*b"moov")), FourCC::from_hex_str_prefixed("0x6d6f6f76"));
+    /// assert_eq!(Ok(FourCC(*b"moov")), FourCC::from_hex_str_prefixed("6d6f6f76"));
+    /// ```
+    pub fn from_hex_str_prefixed(s: &str) -> Result<FourCC, FourCCParseError> {
+        match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(rest) => parse_hex(rest, 2),
+            None => parse_hex(s, 0),
+        }
+        .map_err(|kind| FourCCParseError::new(kind, s))
+    }
+
+    /// Creates a FourCC from a `u32` holding the bytes in big-endian order, so that the
+    /// most-significant byte of `val` becomes the first byte of the code.
+    ///
+    /// This is equivalent to the `From<u32>` implementation, but usable in `const` contexts,
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// const MOOV: FourCC = FourCC::from_u32(0x6d6f6f76);
+    /// assert_eq!(FourCC(*b"moov"), MOOV);
+    /// ```
+    pub const fn from_u32(val: u32) -> FourCC {
+        FourCC(val.to_be_bytes())
+    }
+
+    /// Converts this FourCC into a `u32` holding the bytes in big-endian order.
+    ///
+    /// This is equivalent to the `From<FourCC> for u32` implementation, but usable in `const`
+    /// contexts without the `nightly` feature,
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// const MOOV: u32 = FourCC(*b"moov").to_u32();
+    /// assert_eq!(0x6d6f6f76, MOOV);
+    /// ```
+    pub const fn to_u32(self) -> u32 {
+        u32::from_be_bytes(self.0)
+    }
+
+    /// Creates a FourCC from a `u32` holding the bytes in little-endian order, so that the
+    /// least-significant byte of `val` becomes the first byte of the code.
+    ///
+    /// This is the convention used by RIFF and by Windows multimedia APIs.  Note that the
+    /// `From<u32>` implementation instead uses big-endian order.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(FourCC(*b"WAVE"), FourCC::from_u32_le(0x45564157));
+    /// ```
+    #[doc(alias = "from_le_u32")]
+    pub const fn from_u32_le(val: u32) -> FourCC {
+        FourCC(val.to_le_bytes())
+    }
+
+    /// Converts this FourCC into a `u32` holding the bytes in little-endian order, so that the
+    /// first byte of the code becomes the least-significant byte of the result.
+    ///
+    /// This is the inverse of [`FourCC::from_u32_le()`].
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(0x45564157, FourCC(*b"WAVE").to_u32_le());
+    /// ```
+    #[doc(alias = "to_le_u32")]
+    pub const fn to_u32_le(self) -> u32 {
+        u32::from_le_bytes(self.0)
+    }
+
+    /// Creates a FourCC from a `u32` holding the bytes in the platform's native byte order, as
+    /// when the value was read directly from memory.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let val = u32::from_ne_bytes(*b"avc1");
+    /// assert_eq!(FourCC(*b"avc1"), FourCC::from_u32_ne(val));
+    /// ```
+    #[doc(alias = "from_ne_u32")]
+    pub const fn from_u32_ne(val: u32) -> FourCC {
+        FourCC(val.to_ne_bytes())
+    }
+
+    /// Converts this FourCC into a `u32` holding the bytes in the platform's native byte order.
+    ///
+    /// This is the inverse of [`FourCC::from_u32_ne()`].
+    #[doc(alias = "to_ne_u32")]
+    pub const fn to_u32_ne(self) -> u32 {
+        u32::from_ne_bytes(self.0)
+    }
+
+    /// Returns the code with its bytes in reverse order, the equivalent of `u32::swap_bytes()`.
+    ///
+    /// This converts between codes read from formats which disagree on byte order, such as a
+    /// little-endian Windows `FOURCC` value which has been read as big-endian,
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let misread = FourCC::from(0x32_33_56_59);
+    /// assert_eq!(FourCC(*b"YV32"), misread.reverse_bytes());
+    /// assert_eq!(FourCC(*b"YV32"), FourCC::from_u32_le(0x32_33_56_59));
+    /// ```
+    #[must_use]
+    pub const fn reverse_bytes(self) -> FourCC {
+        FourCC([self.0[3], self.0[2], self.0[1], self.0[0]])
+    }
+
+    /// The same as [`FourCC::reverse_bytes()`], named to match `u32::swap_bytes()`.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// let code = FourCC(*b"YV32");
+    /// assert_eq!(code.to_u32().swap_bytes(), code.swap_bytes().to_u32());
+    /// ```
+    #[must_use]
+    pub const fn swap_bytes(self) -> FourCC {
+        self.reverse_bytes()
+    }
+
+    /// Adds `rhs` to this code, treated as a big-endian `u32`, wrapping around at
+    /// [`FourCC::MAX`].
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(FourCC(*b"tra1"), FourCC(*b"tra0").wrapping_add(1));
+    /// assert_eq!(FourCC(*b"\0\0\0\0"), FourCC::MAX.wrapping_add(1));
+    /// ```
+    #[must_use]
+    pub const fn wrapping_add(self, rhs: u32) -> FourCC {
+        FourCC::from_u32(self.to_u32().wrapping_add(rhs))
+    }
+
+    /// Subtracts `rhs` from this code, treated as a big-endian `u32`, wrapping around at
+    /// [`FourCC::MIN`].
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(FourCC(*b"tra0"), FourCC(*b"tra1").wrapping_sub(1));
+    /// assert_eq!(FourCC::MAX, FourCC::MIN.wrapping_sub(1));
+    /// ```
+    #[must_use]
+    pub const fn wrapping_sub(self, rhs: u32) -> FourCC {
+        FourCC::from_u32(self.to_u32().wrapping_sub(rhs))
+    }
+
+    /// Adds `rhs` to this code, treated as a big-endian `u32`, returning `None` on overflow.
+    ///
+    /// Note that this is byte arithmetic rather than decimal, so that the digit `9` is followed by
+    /// `:`, and carries only propagate into the preceding byte on reaching `0xff`,
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(Some(FourCC(*b"009:")), FourCC(*b"0099").checked_add(1));
+    /// assert_eq!(None, FourCC::MAX.checked_add(1));
+    /// ```
+    #[must_use]
+    pub const fn checked_add(self, rhs: u32) -> Option<FourCC> {
+        match self.to_u32().checked_add(rhs) {
+            Some(val) => Some(FourCC::from_u32(val)),
+            None => None,
+        }
+    }
+
+    /// Subtracts `rhs` from this code, treated as a big-endian `u32`, returning `None` on
+    /// overflow.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(Some(FourCC(*b"tra0")), FourCC(*b"tra1").checked_sub(1));
+    /// assert_eq!(None, FourCC::MIN.checked_sub(1));
+    /// ```
+    #[must_use]
+    pub const fn checked_sub(self, rhs: u32) -> Option<FourCC> {
+        match self.to_u32().checked_sub(rhs) {
+            Some(val) => Some(FourCC::from_u32(val)),
+            None => None,
+        }
+    }
+
+    /// Adds `rhs` to this code, treated as a big-endian `u32`, stopping at [`FourCC::MAX`].
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(FourCC(*b"tra1"), FourCC(*b"tra0").saturating_add(1));
+    /// assert_eq!(FourCC::MAX, FourCC::MAX.saturating_add(1));
+    /// ```
+    #[must_use]
+    pub const fn saturating_add(self, rhs: u32) -> FourCC {
+        FourCC::from_u32(self.to_u32().saturating_add(rhs))
+    }
+
+    /// Subtracts `rhs` from this code, treated as a big-endian `u32`, stopping at
+    /// [`FourCC::MIN`].
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(FourCC(*b"tra0"), FourCC(*b"tra1").saturating_sub(1));
+    /// assert_eq!(FourCC::MIN, FourCC::MIN.saturating_sub(1));
+    /// ```
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: u32) -> FourCC {
+        FourCC::from_u32(self.to_u32().saturating_sub(rhs))
+    }
+}
+/// The default value is [`FourCC::ZERO`], the all-zero code.
+///
+/// Zero is used rather than four spaces since it is the conventional "no value" marker in the
+/// formats that have one, and matches the default of the equivalent `u32`.
+impl Default for FourCC {
+    fn default() -> Self {
+        FourCC::ZERO
+    }
+}
+impl From<[u8; 4]> for FourCC {
+    fn from(buf: [u8; 4]) -> FourCC {
+        FourCC(buf)
+    }
+}
+impl From<FourCC> for [u8; 4] {
+    fn from(val: FourCC) -> Self {
+        val.to_array()
     }
 }
-impl<'a> From<&'a [u8; 4]> for FourCC {
+impl From<&[u8; 4]> for FourCC {
     fn from(buf: &[u8; 4]) -> FourCC {
         FourCC([buf[0], buf[1], buf[2], buf[3]])
     }
 }
-impl<'a> From<&'a [u8]> for FourCC {
+/// **Deprecated:** use [`FourCC::try_from_slice()`], which does not panic on short slices.
+///
+/// Only the first four bytes are used, and any further bytes are silently ignored.
+///
+/// # Panics
+///
+/// Panics if the slice is shorter than four bytes.  Note that this also applies to
+/// `FourCC::try_from(slice)`, which the standard library provides in terms of this implementation.
+impl From<&[u8]> for FourCC {
     fn from(buf: &[u8]) -> FourCC {
         FourCC([buf[0], buf[1], buf[2], buf[3]])
     }
 }
+#[cfg(feature = "alloc")]
+impl TryFrom<&alloc::vec::Vec<u8>> for FourCC {
+    type Error = FourCCFromSliceError;
+    fn try_from(buf: &alloc::vec::Vec<u8>) -> Result<Self, Self::Error> {
+        FourCC::try_from_slice(buf)
+    }
+}
+#[cfg(feature = "alloc")]
+impl TryFrom<alloc::vec::Vec<u8>> for FourCC {
+    type Error = FourCCFromSliceError;
+    fn try_from(buf: alloc::vec::Vec<u8>) -> Result<Self, Self::Error> {
+        FourCC::try_from_slice(&buf)
+    }
+}
+/// Interprets the value in big-endian order (as used by ISOBMFF, and network byte order), so that
+/// the most-significant byte becomes the first byte of the code.
 impl From<u32> for FourCC {
     fn from(val: u32) -> FourCC {
-        FourCC([
-            (val >> 24 & 0xff) as u8,
-            (val >> 16 & 0xff) as u8,
-            (val >> 8 & 0xff) as u8,
-            (val & 0xff) as u8,
-        ])
+        FourCC::from_u32(val)
+    }
+}
+impl PartialEq<[u8; 4]> for FourCC {
+    fn eq(&self, other: &[u8; 4]) -> bool {
+        self.0 == *other
+    }
+}
+impl PartialEq<FourCC> for [u8; 4] {
+    fn eq(&self, other: &FourCC) -> bool {
+        *self == other.0
+    }
+}
+/// Compares against the big-endian interpretation of the `u32`, consistent with `From<u32>`.
+impl PartialEq<u32> for FourCC {
+    fn eq(&self, other: &u32) -> bool {
+        self.to_u32() == *other
+    }
+}
+impl PartialEq<FourCC> for u32 {
+    fn eq(&self, other: &FourCC) -> bool {
+        *self == other.to_u32()
+    }
+}
+/// Compares the raw bytes of the string, so strings that are not exactly four bytes long are
+/// never equal.
+impl PartialEq<str> for FourCC {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other.as_bytes()
+    }
+}
+impl PartialEq<&str> for FourCC {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+impl PartialEq<FourCC> for str {
+    fn eq(&self, other: &FourCC) -> bool {
+        *other == *self
+    }
+}
+impl PartialEq<FourCC> for &str {
+    fn eq(&self, other: &FourCC) -> bool {
+        *other == **self
+    }
+}
+impl AsRef<[u8]> for FourCC {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+impl AsRef<[u8; 4]> for FourCC {
+    fn as_ref(&self) -> &[u8; 4] {
+        &self.0
+    }
+}
+// `Hash`, `Eq` and `Ord` for FourCC all agree with those of `[u8]` and `[u8; 4]`, as required by
+// `Borrow`
+impl Borrow<[u8]> for FourCC {
+    fn borrow(&self) -> &[u8] {
+        &self.0
+    }
+}
+impl Borrow<[u8; 4]> for FourCC {
+    fn borrow(&self) -> &[u8; 4] {
+        &self.0
+    }
+}
+impl IntoIterator for FourCC {
+    type Item = u8;
+    type IntoIter = core::array::IntoIter<u8, 4>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+impl<'a> IntoIterator for &'a FourCC {
+    type Item = &'a u8;
+    type IntoIter = core::slice::Iter<'a, u8>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+impl Index<usize> for FourCC {
+    type Output = u8;
+    fn index(&self, index: usize) -> &u8 {
+        &self.0[index]
+    }
+}
+impl IndexMut<usize> for FourCC {
+    fn index_mut(&mut self, index: usize) -> &mut u8 {
+        &mut self.0[index]
     }
 }
 impl PartialOrd for FourCC {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        // Implement comparison logic here, possibly using the inner FourCC value
-        // For example, if FourCC can be converted to something comparable:
-        self.to_string().partial_cmp(&other.to_string())
+        Some(self.cmp(other))
     }
 }
 impl Ord for FourCC {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.to_string().cmp(&other.to_string())
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Implements a bitwise operator for `FourCC` with `FourCC`, and with a big-endian `u32`, plus
+/// the assigning equivalents.
+macro_rules! bit_op {
+    ($op:ident, $method:ident, $assign_op:ident, $assign_method:ident) => {
+        impl $op for FourCC {
+            type Output = FourCC;
+            fn $method(self, rhs: FourCC) -> FourCC {
+                FourCC::from_u32(self.to_u32().$method(rhs.to_u32()))
+            }
+        }
+        impl $op<u32> for FourCC {
+            type Output = FourCC;
+            fn $method(self, rhs: u32) -> FourCC {
+                FourCC::from_u32(self.to_u32().$method(rhs))
+            }
+        }
+        impl $assign_op for FourCC {
+            fn $assign_method(&mut self, rhs: FourCC) {
+                *self = $op::$method(*self, rhs);
+            }
+        }
+        impl $assign_op<u32> for FourCC {
+            fn $assign_method(&mut self, rhs: u32) {
+                *self = $op::$method(*self, rhs);
+            }
+        }
+    };
+}
+bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+impl Not for FourCC {
+    type Output = FourCC;
+    fn not(self) -> FourCC {
+        FourCC::from_u32(!self.to_u32())
     }
 }
 impl FromStr for FourCC {
-    type Err = u32;
+    type Err = FourCCParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.len() != 4 {
-            return Err(s.len() as u32);
+            let kind = FourCCParseErrorKind::WrongLength { found: s.len() };
+            return Err(FourCCParseError::new(kind, s));
+        }
+        if let Some(position) = s.bytes().position(|b| !b.is_ascii()) {
+            let kind = FourCCParseErrorKind::NonAscii { position };
+            return Err(FourCCParseError::new(kind, s));
         }
         let mut buf = [0u8; 4];
         buf.copy_from_slice(s.as_bytes());
         Ok(FourCC(buf))
     }
 }
+/// Equivalent to the `FromStr` implementation, for use with generic code bounded on `TryFrom`.
+impl TryFrom<&str> for FourCC {
+    type Error = FourCCParseError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// The error returned when attempting to create a [`FourCC`] from a slice that is not exactly
+/// four bytes long.
+///
+/// Short input is a normal condition when parsing untrusted data, so the error records the length
+/// actually supplied to allow a useful diagnostic,
+///
+/// ```rust
+/// # use four_cc::FourCC;
+/// let truncated_header = b"\0\0\0\x08mo";
+/// match FourCC::try_from_slice(&truncated_header[4..]) {
+///     Ok(code) => println!("box type {}", code),
+///     Err(e) => {
+///         # assert_eq!(2, e.actual_len());
+///         println!("truncated box header: {}", e);  // produces: expected 4 bytes for FourCC, got 2
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FourCCFromSliceError {
+    actual: usize,
+}
+impl FourCCFromSliceError {
+    /// The length of the slice that was supplied.
+    pub fn actual_len(&self) -> usize {
+        self.actual
+    }
+}
+impl fmt::Display for FourCCFromSliceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected 4 bytes for FourCC, got {}", self.actual)
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for FourCCFromSliceError {}
+
+/// Error returned by [`FourCC::try_from_iter()`] when the iterator does not yield exactly four
+/// bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FourCCFromIterError {
+    /// The iterator ended before yielding four bytes.
+    TooShort {
+        /// The number of bytes the iterator yielded.
+        found: usize,
+    },
+    /// The iterator yielded more than four bytes.
+    TooLong,
+}
+impl fmt::Display for FourCCFromIterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FourCCFromIterError::TooShort { found } => {
+                write!(f, "expected 4 bytes for FourCC, got {}", found)
+            }
+            FourCCFromIterError::TooLong => f.write_str("expected 4 bytes for FourCC, got more"),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for FourCCFromIterError {}
+
+/// The error returned when parsing a [`FourCC`] from a string fails.
+///
+/// Parsing requires exactly four ASCII characters.  Non-ASCII characters are rejected, since
+/// their multi-byte UTF-8 encoding does not correspond to a meaningful FourCC value.
+///
+/// The error records what went wrong, as a [`FourCCParseErrorKind`], along with a copy of the
+/// input (truncated to its first 32 bytes), which is quoted in the `Display` output,
+///
+/// ```rust
+/// # use four_cc::{FourCC, FourCCParseErrorKind};
+/// let err = "moo".parse::<FourCC>().unwrap_err();
+/// assert_eq!(FourCCParseErrorKind::WrongLength { found: 3 }, err.kind());
+/// assert_eq!("moo", err.input());
+/// # #[cfg(feature = "std")]
+/// assert_eq!("expected 4 bytes for FourCC, found 3 in \"moo\"", err.to_string());
+/// ```
+///
+/// With the `std` feature enabled, this type implements `std::error::Error`, so it can be
+/// propagated with `?` alongside other errors.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct FourCCParseError {
+    kind: FourCCParseErrorKind,
+    input: [u8; FourCCParseError::MAX_INPUT],
+    input_len: u8,
+    truncated: bool,
+}
+impl FourCCParseError {
+    const MAX_INPUT: usize = 32;
+
+    fn new(kind: FourCCParseErrorKind, s: &str) -> FourCCParseError {
+        let mut len = s.len().min(FourCCParseError::MAX_INPUT);
+        while !s.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut input = [0u8; FourCCParseError::MAX_INPUT];
+        input[..len].copy_from_slice(&s.as_bytes()[..len]);
+        FourCCParseError {
+            kind,
+            input,
+            input_len: len as u8,
+            truncated: len < s.len(),
+        }
+    }
+
+    /// The reason that parsing failed.
+    pub fn kind(&self) -> FourCCParseErrorKind {
+        self.kind
+    }
+
+    /// The string that could not be parsed, truncated to its first 32 bytes (at a character
+    /// boundary) if it was longer.
+    pub fn input(&self) -> &str {
+        core::str::from_utf8(&self.input[..self.input_len as usize])
+            .expect("input is truncated at a char boundary")
+    }
+}
+impl fmt::Debug for FourCCParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FourCCParseError")
+            .field("kind", &self.kind)
+            .field("input", &self.input())
+            .finish()
+    }
+}
+impl fmt::Display for FourCCParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in {:?}", self.kind, self.input())?;
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for FourCCParseError {}
+
+/// The reason that parsing a [`FourCC`] from a string failed, as returned by
+/// [`FourCCParseError::kind()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FourCCParseErrorKind {
+    /// The string was not exactly four bytes long.
+    WrongLength {
+        /// The length of the string, in bytes.
+        found: usize,
+    },
+    /// The string contained a non-ASCII character.
+    NonAscii {
+        /// The byte offset of the first non-ASCII character.
+        position: usize,
+    },
+    /// The string contained a backslash which did not begin a valid escape sequence.
+    InvalidEscape {
+        /// The byte offset of the backslash.
+        position: usize,
+    },
+    /// The string was expected to contain exactly eight hex digits, but had a different length.
+    WrongHexLength {
+        /// The length of the string (excluding any prefix), in bytes.
+        found: usize,
+    },
+    /// The string was expected to contain only hex digits, but contained some other character.
+    InvalidHexDigit {
+        /// The byte offset of the first character that is not a hex digit.
+        position: usize,
+    },
+}
+/// Describes the problem, without quoting the input.
+impl fmt::Display for FourCCParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FourCCParseErrorKind::WrongLength { found } => {
+                write!(f, "expected 4 bytes for FourCC, found {}", found)
+            }
+            FourCCParseErrorKind::NonAscii { position } => {
+                write!(f, "non-ASCII character in FourCC at byte {}", position)
+            }
+            FourCCParseErrorKind::InvalidEscape { position } => {
+                write!(f, "invalid escape sequence in FourCC at byte {}", position)
+            }
+            FourCCParseErrorKind::WrongHexLength { found } => {
+                write!(f, "expected 8 hex digits for FourCC, found {} bytes", found)
+            }
+            FourCCParseErrorKind::InvalidHexDigit { position } => {
+                write!(f, "invalid hex digit in FourCC at byte {}", position)
+            }
+        }
+    }
+}
 
 // The macro is needed, because the `impl const` syntax doesn't exists on `stable`.
 #[cfg(not(feature = "nightly"))]
@@ -171,7 +1204,7 @@ macro_rules! from_fourcc_for_u32 {
     () => {
         impl From<FourCC> for u32 {
             fn from(val: FourCC) -> Self {
-                val.from_u32()
+                val.to_u32()
             }
         }
     };
@@ -181,113 +1214,1317 @@ macro_rules! from_fourcc_for_u32 {
     ($($t:tt)*) => {
         impl const From<FourCC> for u32 {
             fn from(val: FourCC) -> Self {
-                val.from_u32()
+                val.to_u32()
             }
         }
     };
 }
 from_fourcc_for_u32!();
 
+/// Non-printable bytes are escaped, as by [`core::ascii::escape_default()`].  Width, fill,
+/// alignment and precision are applied to the escaped text.
 impl fmt::Display for FourCC {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        let b = &self.0;
-        let iter = core::ascii::escape_default(b[0])
-            .chain(core::ascii::escape_default(b[1]))
-            .chain(core::ascii::escape_default(b[2]))
-            .chain(core::ascii::escape_default(b[3]));
-        for c in iter {
-            f.write_char(c as char)?;
-        }
-        Ok(())
+        let (buf, len) = escape_ascii(self);
+        // the escaped text is all ASCII
+        let s = core::str::from_utf8(&buf[..len]).map_err(|_| fmt::Error)?;
+        f.pad(s)
     }
 }
 
-impl fmt::Debug for FourCC {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        f.debug_tuple("FourCC")
-            .field(&format_args!("{}", self))
-            .finish()
+/// Escapes the bytes of the code as by `core::ascii::escape_default()`, returning the buffer and
+/// the length of the escaped text within it.
+fn escape_ascii(code: &FourCC) -> ([u8; 16], usize) {
+    // each byte escapes to at most four characters, e.g. `\xff`
+    let mut buf = [0u8; 16];
+    let mut len = 0;
+    for b in code.0.iter().flat_map(|&b| core::ascii::escape_default(b)) {
+        buf[len] = b;
+        len += 1;
     }
+    (buf, len)
 }
 
-#[cfg(feature = "schemars")]
-impl schemars::JsonSchema for FourCC {
-    fn schema_name() -> String {
-        "FourCC".to_string()
+/// Parses the escaped representation produced by the `Display` implementation, so that
+/// serialisation round-trips even for codes containing non-printable bytes.
+#[cfg(any(feature = "serde", feature = "sqlx"))]
+fn parse_escaped(s: &str) -> Result<FourCC, FourCCParseError> {
+    parse_escaped_kind(s).map_err(|kind| FourCCParseError::new(kind, s))
+}
+
+#[cfg(any(feature = "serde", feature = "sqlx"))]
+fn parse_escaped_kind(s: &str) -> Result<FourCC, FourCCParseErrorKind> {
+    fn hex_digit(b: u8) -> Option<u8> {
+        match b {
+            b'0'..=b'9' => Some(b - b'0'),
+            b'a'..=b'f' => Some(b - b'a' + 10),
+            b'A'..=b'F' => Some(b - b'A' + 10),
+            _ => None,
+        }
     }
-    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
-        gen.subschema_for::<&str>()
+    if let Some(position) = s.bytes().position(|b| !b.is_ascii()) {
+        return Err(FourCCParseErrorKind::NonAscii { position });
     }
-    fn is_referenceable() -> bool {
-        false
+    let input = s.as_bytes();
+    let mut buf = [0u8; 4];
+    let mut found = 0;
+    let mut i = 0;
+    while i < input.len() {
+        let (b, len) = if input[i] == b'\\' {
+            match input.get(i + 1) {
+                Some(b't') => (b'\t', 2),
+                Some(b'r') => (b'\r', 2),
+                Some(b'n') => (b'\n', 2),
+                Some(&c @ (b'\\' | b'\'' | b'"')) => (c, 2),
+                Some(b'x') => {
+                    let hi = input.get(i + 2).and_then(|&c| hex_digit(c));
+                    let lo = input.get(i + 3).and_then(|&c| hex_digit(c));
+                    match (hi, lo) {
+                        (Some(hi), Some(lo)) => (hi << 4 | lo, 4),
+                        _ => return Err(FourCCParseErrorKind::InvalidEscape { position: i }),
+                    }
+                }
+                _ => return Err(FourCCParseErrorKind::InvalidEscape { position: i }),
+            }
+        } else {
+            (input[i], 1)
+        };
+        if found < buf.len() {
+            buf[found] = b;
+        }
+        found += 1;
+        i += len;
     }
-}
-
-#[cfg(feature = "serde")]
-impl serde::ser::Serialize for FourCC {
-    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        serializer.collect_str(self)
+    if found != buf.len() {
+        return Err(FourCCParseErrorKind::WrongLength { found });
     }
+    Ok(FourCC(buf))
 }
 
-#[cfg(feature = "serde")]
-struct FromStrVisitor<T> {
-    expecting: &'static str,
-    ty: core::marker::PhantomData<T>,
-}
-
-#[cfg(feature = "serde")]
-impl<T> FromStrVisitor<T> {
-    fn new(expecting: &'static str) -> Self {
-        FromStrVisitor {
-            expecting: expecting,
-            ty: core::marker::PhantomData,
+/// Formats the code in the same way as the `Display` implementation, escaping any bytes which
+/// are not printable ASCII.
+///
+/// _Requires the `defmt` feature._
+#[cfg(feature = "defmt")]
+impl defmt::Format for FourCC {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        let (buf, len) = escape_ascii(self);
+        // the escaped text is all ASCII, so this always succeeds
+        if let Ok(s) = core::str::from_utf8(&buf[..len]) {
+            defmt::write!(f, "{=str}", s)
         }
     }
 }
 
-#[cfg(feature = "serde")]
-impl<'de, T> serde::de::Visitor<'de> for FromStrVisitor<T>
-where
-    T: core::str::FromStr,
-    T::Err: fmt::Display,
-{
-    type Value = T;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        formatter.write_str(self.expecting)
+impl fmt::Debug for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("FourCC")
+            .field(&format_args!("{}", self))
+            .finish()
     }
+}
 
-    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        s.parse().map_err(serde::de::Error::custom)
-    }
+/// Formats the code as eight hex digits, with any leading zeros, and with a `0x` prefix if the
+/// alternate flag (`{:#x}`) is given.
+fn fmt_hex(code: &FourCC, f: &mut fmt::Formatter<'_>, digits: &[u8; 16]) -> fmt::Result {
+    let buf = hex_digits(code, digits);
+    // the digits are all ASCII
+    let s = core::str::from_utf8(&buf).map_err(|_| fmt::Error)?;
+    f.pad_integral(true, "0x", s)
 }
 
-#[cfg(feature = "serde")]
-impl<'de> serde::de::Deserialize<'de> for FourCC {
-    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        deserializer.deserialize_str(FromStrVisitor::new("FourCC"))
+const fn hex_digits(code: &FourCC, digits: &[u8; 16]) -> [u8; 8] {
+    let mut buf = [0u8; 8];
+    let mut i = 0;
+    while i < code.0.len() {
+        buf[i * 2] = digits[(code.0[i] >> 4) as usize];
+        buf[i * 2 + 1] = digits[(code.0[i] & 0xf) as usize];
+        i += 1;
     }
+    buf
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Parses eight hex digits, where `offset` is the position of `s` within the original input,
+/// for error reporting.
+fn parse_hex(s: &str, offset: usize) -> Result<FourCC, FourCCParseErrorKind> {
+    fn digit(s: &[u8], i: usize, offset: usize) -> Result<u8, FourCCParseErrorKind> {
+        match s[i] {
+            b'0'..=b'9' => Ok(s[i] - b'0'),
+            b'a'..=b'f' => Ok(s[i] - b'a' + 10),
+            b'A'..=b'F' => Ok(s[i] - b'A' + 10),
+            _ => Err(FourCCParseErrorKind::InvalidHexDigit {
+                position: offset + i,
+            }),
+        }
+    }
+    let s = s.as_bytes();
+    if s.len() != 8 {
+        return Err(FourCCParseErrorKind::WrongHexLength { found: s.len() });
+    }
+    let mut buf = [0u8; 4];
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = digit(s, i * 2, offset)? << 4 | digit(s, i * 2 + 1, offset)?;
+    }
+    Ok(FourCC(buf))
+}
+
+/// The hex representation of a [`FourCC`], as returned by [`FourCC::to_hex_string()`].
+///
+/// This dereferences to a `&str` of eight lowercase hex digits, and is stored inline, so that no
+/// allocation is required.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexString([u8; 8]);
+impl HexString {
+    /// Returns the hex digits as a string slice.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.0).expect("hex digits are ASCII")
+    }
+}
+impl core::ops::Deref for HexString {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl AsRef<str> for HexString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl fmt::Display for HexString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+impl fmt::Debug for HexString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::LowerHex for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt_hex(self, f, b"0123456789abcdef")
+    }
+}
+
+impl fmt::UpperHex for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt_hex(self, f, b"0123456789ABCDEF")
+    }
+}
+
+/// Formats the big-endian `u32` value of `code` using `bits` bits per digit, always producing
+/// enough digits for all 32 bits.
+fn fmt_radix(code: &FourCC, f: &mut fmt::Formatter<'_>, bits: u32, prefix: &str) -> fmt::Result {
+    let mut buf = [0u8; 32];
+    let len = 32u32.div_ceil(bits) as usize;
+    let val = code.to_u32();
+    for (i, b) in buf[..len].iter_mut().rev().enumerate() {
+        *b = b'0' + (val >> (i as u32 * bits) & ((1 << bits) - 1)) as u8;
+    }
+    // the digits are all ASCII
+    let s = core::str::from_utf8(&buf[..len]).map_err(|_| fmt::Error)?;
+    f.pad_integral(true, prefix, s)
+}
+
+/// Formats the code as 32 binary digits, e.g. `{:#b}` gives
+/// `0b01101101011011110110111101110110` for `moov`.
+impl fmt::Binary for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt_radix(self, f, 1, "0b")
+    }
+}
+/// Formats the code as 11 octal digits, e.g. `{:#o}` gives `0o15533667566` for `moov`.
+impl fmt::Octal for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt_radix(self, f, 3, "0o")
+    }
+}
+
+#[cfg(any(feature = "schemars", feature = "schemars1"))]
+const SCHEMA_DESCRIPTION: &str = "A four-character-code.  Bytes other than printable ASCII, and \
+    the characters \\, ' and \", are escaped as \\t, \\r, \\n, \\\\, \\', \\\" or \\xNN.";
+// four bytes, each of which may be escaped as up to four characters
+#[cfg(any(feature = "schemars", feature = "schemars1"))]
+const SCHEMA_MIN_LENGTH: u32 = 4;
+#[cfg(any(feature = "schemars", feature = "schemars1"))]
+const SCHEMA_MAX_LENGTH: u32 = 16;
+#[cfg(any(feature = "schemars", feature = "schemars1"))]
+const SCHEMA_PATTERN: &str = r#"^(?:[ !#-&(-\[\]-~]|\\[trn\\'"]|\\x[0-9a-fA-F]{2}){4}$"#;
+#[cfg(any(feature = "schemars", feature = "schemars1"))]
+const SCHEMA_EXAMPLES: [&str; 3] = ["moov", "jp2 ", "\\xa9nam"];
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for FourCC {
+    fn schema_name() -> String {
+        "FourCC".to_string()
+    }
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{InstanceType, Metadata, SchemaObject, StringValidation};
+
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(SCHEMA_DESCRIPTION.to_string()),
+                examples: SCHEMA_EXAMPLES.iter().map(|&e| e.into()).collect(),
+                ..Default::default()
+            })),
+            instance_type: Some(InstanceType::String.into()),
+            format: Some("four-cc".to_string()),
+            string: Some(Box::new(StringValidation {
+                min_length: Some(SCHEMA_MIN_LENGTH),
+                max_length: Some(SCHEMA_MAX_LENGTH),
+                pattern: Some(SCHEMA_PATTERN.to_string()),
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+    fn is_referenceable() -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "schemars1")]
+impl schemars1::JsonSchema for FourCC {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "FourCC".into()
+    }
+    fn json_schema(_gen: &mut schemars1::SchemaGenerator) -> schemars1::Schema {
+        schemars1::json_schema!({
+            "type": "string",
+            "format": "four-cc",
+            "description": SCHEMA_DESCRIPTION,
+            "minLength": SCHEMA_MIN_LENGTH,
+            "maxLength": SCHEMA_MAX_LENGTH,
+            "pattern": SCHEMA_PATTERN,
+            "examples": SCHEMA_EXAMPLES,
+        })
+    }
+    fn inline_schema() -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl FourCC {
+    /// Builds a code from four raw bytes, as taken from fuzzer input, biased towards printable
+    /// ASCII.
+    ///
+    /// When both of the top two bits of the first byte are set (i.e. for a quarter of all
+    /// inputs) each byte is mapped into the printable ASCII range, so that fuzzing reaches the
+    /// code paths handling well-known codes more quickly.  Otherwise the bytes are used as-is.
+    ///
+    /// ```rust
+    /// # use four_cc::FourCC;
+    /// assert_eq!(FourCC(*b"\0\xffab"), FourCC::from_arbitrary_bytes(*b"\0\xffab"));
+    /// assert!(FourCC::from_arbitrary_bytes(*b"\xff\0\x80\x7f").is_printable());
+    /// ```
+    ///
+    /// _Requires the `arbitrary` feature._
+    pub const fn from_arbitrary_bytes(bytes: [u8; 4]) -> FourCC {
+        if bytes[0] & 0xc0 != 0xc0 {
+            return FourCC(bytes);
+        }
+        let mut printable = [0; 4];
+        let mut i = 0;
+        while i < bytes.len() {
+            printable[i] = b' ' + bytes[i] % (b'~' - b' ' + 1);
+            i += 1;
+        }
+        FourCC(printable)
+    }
+}
+
+/// Consumes exactly four bytes of input, passing them to [`FourCC::from_arbitrary_bytes()`].
+///
+/// _Requires the `arbitrary` feature._
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for FourCC {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(FourCC::from_arbitrary_bytes(u.arbitrary()?))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (4, Some(4))
+    }
+}
+
+/// Generates codes from random bytes.
+///
+/// Shrinking moves each byte towards zero, except for lowercase ASCII letters, which shrink
+/// towards `b'a'` so that values resembling box names stay readable.
+///
+/// _Requires the `quickcheck` feature._
+#[cfg(feature = "quickcheck")]
+impl quickcheck::Arbitrary for FourCC {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        FourCC([
+            u8::arbitrary(g),
+            u8::arbitrary(g),
+            u8::arbitrary(g),
+            u8::arbitrary(g),
+        ])
+    }
+
+    fn shrink(&self) -> alloc::boxed::Box<dyn Iterator<Item = Self>> {
+        use alloc::boxed::Box;
+
+        let code = *self;
+        Box::new((0..4).flat_map(move |i| {
+            let b = code.0[i];
+            let shrunk: Box<dyn Iterator<Item = u8>> = if b.is_ascii_lowercase() {
+                Box::new((b - b'a').shrink().map(|d| b'a' + d))
+            } else {
+                b.shrink()
+            };
+            shrunk.map(move |b| {
+                let mut code = code;
+                code.0[i] = b;
+                code
+            })
+        }))
+    }
+}
+
+/// Implements deku's reader and writer traits for a context type, ignoring the context's value so
+/// that the four bytes are always read and written in order.
+#[cfg(feature = "deku")]
+macro_rules! deku_impls {
+    ($ctx:ty) => {
+        impl<'a> deku::DekuReader<'a, $ctx> for FourCC {
+            fn from_reader_with_ctx<R: deku::no_std_io::Read + deku::no_std_io::Seek>(
+                reader: &mut deku::reader::Reader<R>,
+                _ctx: $ctx,
+            ) -> Result<Self, deku::DekuError> {
+                <[u8; 4]>::from_reader_with_ctx(reader, ()).map(FourCC)
+            }
+        }
+        impl deku::DekuWriter<$ctx> for FourCC {
+            fn to_writer<W: deku::no_std_io::Write + deku::no_std_io::Seek>(
+                &self,
+                writer: &mut deku::writer::Writer<W>,
+                _ctx: $ctx,
+            ) -> Result<(), deku::DekuError> {
+                self.0.to_writer(writer, ())
+            }
+        }
+    };
+}
+#[cfg(feature = "deku")]
+deku_impls!(());
+#[cfg(feature = "deku")]
+deku_impls!(deku::ctx::Endian);
+
+/// Reads the four bytes of the code verbatim, regardless of the `Endian` argument.
+///
+/// _Requires the `binrw` feature._
+#[cfg(feature = "binrw")]
+impl binrw::BinRead for FourCC {
+    type Args<'a> = ();
+
+    fn read_options<R: binrw::io::Read + binrw::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> binrw::BinResult<Self> {
+        <[u8; 4]>::read_options(reader, endian, args).map(FourCC)
+    }
+}
+
+/// Writes the four bytes of the code verbatim, regardless of the `Endian` argument.
+///
+/// _Requires the `binrw` feature._
+#[cfg(feature = "binrw")]
+impl binrw::BinWrite for FourCC {
+    type Args<'a> = ();
+
+    fn write_options<W: binrw::io::Write + binrw::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> binrw::BinResult<()> {
+        self.0.write_options(writer, endian, args)
+    }
+}
+
+/// Stores the code as a four character string, in `TEXT` columns with SQLite, or `TEXT`,
+/// `VARCHAR` or `CHAR(4)` columns with PostgreSQL, e.g.
+///
+/// ```sql
+/// CREATE TABLE tracks (
+///     id INTEGER PRIMARY KEY NOT NULL,
+///     codec CHAR(4) NOT NULL
+/// );
+/// ```
+///
+/// Fails if the code is not ASCII.
+///
+/// _Requires the `diesel` feature._
+#[cfg(feature = "diesel")]
+impl<DB> diesel::serialize::ToSql<diesel::sql_types::Text, DB> for FourCC
+where
+    DB: diesel::backend::Backend,
+    str: diesel::serialize::ToSql<diesel::sql_types::Text, DB>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, DB>,
+    ) -> diesel::serialize::Result {
+        match self.as_str() {
+            Ok(s) if self.is_ascii() => s.to_sql(out),
+            _ => Err(format!("FourCC {} is not ASCII", self).into()),
+        }
+    }
+}
+
+/// Reads the code from a four character string, as by the `FromStr` implementation.
+///
+/// _Requires the `diesel` feature._
+#[cfg(feature = "diesel")]
+impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for FourCC
+where
+    DB: diesel::backend::Backend,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        let s = String::from_sql(bytes)?;
+        Ok(s.parse()?)
+    }
+}
+
+/// Reads the four bytes of the code verbatim, regardless of the endianness, so that
+/// `bytes.pread::<FourCC>(offset)` works.
+///
+/// _Requires the `scroll` feature._
+#[cfg(feature = "scroll")]
+impl<'a> scroll::ctx::TryFromCtx<'a, scroll::Endian> for FourCC {
+    type Error = scroll::Error;
+
+    fn try_from_ctx(src: &'a [u8], _ctx: scroll::Endian) -> Result<(Self, usize), Self::Error> {
+        match src.first_chunk() {
+            Some(bytes) => Ok((FourCC(*bytes), 4)),
+            None => Err(scroll::Error::TooBig {
+                size: 4,
+                len: src.len(),
+            }),
+        }
+    }
+}
+
+/// Writes the four bytes of the code verbatim, regardless of the endianness, so that
+/// `bytes.pwrite(code, offset)` works.
+///
+/// _Requires the `scroll` feature._
+#[cfg(feature = "scroll")]
+impl scroll::ctx::TryIntoCtx<scroll::Endian> for FourCC {
+    type Error = scroll::Error;
+
+    fn try_into_ctx(self, dst: &mut [u8], _ctx: scroll::Endian) -> Result<usize, Self::Error> {
+        let len = dst.len();
+        match dst.first_chunk_mut() {
+            Some(bytes) => {
+                *bytes = self.0;
+                Ok(4)
+            }
+            None => Err(scroll::Error::TooBig { size: 4, len }),
+        }
+    }
+}
+
+/// _Requires the `scroll` feature._
+#[cfg(feature = "scroll")]
+impl scroll::ctx::SizeWith<scroll::Endian> for FourCC {
+    fn size_with(_ctx: &scroll::Endian) -> usize {
+        4
+    }
+}
+
+/// Reads the four bytes of the code verbatim, regardless of the context's endianness.
+///
+/// _Requires the `speedy` feature._
+#[cfg(feature = "speedy")]
+impl<'a, C: speedy::Context> speedy::Readable<'a, C> for FourCC {
+    fn read_from<R: speedy::Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        let mut buf = [0u8; 4];
+        reader.read_bytes(&mut buf)?;
+        Ok(FourCC(buf))
+    }
+
+    fn minimum_bytes_needed() -> usize {
+        4
+    }
+}
+
+/// Writes the four bytes of the code verbatim, regardless of the context's endianness.
+///
+/// _Requires the `speedy` feature._
+#[cfg(feature = "speedy")]
+impl<C: speedy::Context> speedy::Writable<C> for FourCC {
+    fn write_to<T: ?Sized + speedy::Writer<C>>(&self, writer: &mut T) -> Result<(), C::Error> {
+        writer.write_bytes(&self.0)
+    }
+
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Ok(4)
+    }
+}
+
+#[cfg(feature = "bytes")]
+pub mod bytes;
+
+#[cfg(feature = "embedded-io")]
+mod embedded_io;
+
+#[cfg(feature = "futures-io")]
+pub mod futures_io;
+
+#[cfg(feature = "std")]
+pub mod io;
+
+#[cfg(feature = "isobmff")]
+pub mod isobmff;
+
+#[cfg(feature = "nom")]
+pub mod nom;
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+#[cfg(feature = "rand")]
+pub mod rand;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+#[cfg(feature = "sqlx")]
+pub mod sqlx;
+
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+#[cfg(feature = "winnow")]
+pub mod winnow;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eq() {
+        let uuid: FourCC = b"uuid".into();
+        let diuu: FourCC = b"diuu".into();
+        assert_eq!(FourCC(*b"uuid"), uuid);
+        assert_ne!(FourCC(*b"uuid"), diuu);
+    }
+
+    #[test]
+    fn const_eq() {
+        const MOOV: FourCC = FourCC(*b"moov");
+        const MOOF: FourCC = FourCC(*b"moof");
+        const _: () = assert!(MOOV.const_eq(&MOOV));
+        const _: () = assert!(!MOOV.const_eq(&MOOF));
+        const _: () = assert!(!MOOV.const_eq(&MOOV.reverse_bytes()));
+        assert!(FourCC::ZERO.const_eq(&FourCC::MIN));
+        assert!(!FourCC::MIN.const_eq(&FourCC::MAX));
+    }
+
+    #[test]
+    fn as_ref() {
+        fn len<T: AsRef<[u8]>>(data: T) -> usize {
+            data.as_ref().len()
+        }
+        assert_eq!(4, len(FourCC(*b"moov")));
+        let code = FourCC(*b"moov");
+        let array: &[u8; 4] = code.as_ref();
+        assert_eq!(b"moov", array);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn borrow() {
+        let mut map = std::collections::HashMap::new();
+        map.insert(FourCC(*b"moov"), "movie");
+        assert_eq!(Some(&"movie"), map.get(&b"moov"[..]));
+        assert_eq!(None, map.get(&b"trak"[..]));
+        let set: std::collections::BTreeSet<_> = [FourCC(*b"moov")].into_iter().collect();
+        assert!(set.contains(&b"moov"[..]));
+
+        // lookup by array, without converting to a slice
+        assert_eq!(Some(&"movie"), map.get(b"moov"));
+        assert_eq!(None, map.get(b"trak"));
+        assert!(set.contains(b"moov"));
+        let key: [u8; 4] = *b"moov";
+        assert_eq!(Some("movie"), map.remove(&key));
+
+        // the hashes must agree for lookups to work
+        fn hash<T: std::hash::Hash + ?Sized>(value: &T) -> u64 {
+            use std::hash::{BuildHasher, RandomState};
+            static STATE: std::sync::OnceLock<RandomState> = std::sync::OnceLock::new();
+            STATE.get_or_init(RandomState::new).hash_one(value)
+        }
+        let code = FourCC(*b"\xa9nam");
+        assert_eq!(hash(&code), hash::<[u8; 4]>(code.borrow()));
+        assert_eq!(hash(&code), hash::<[u8]>(code.borrow()));
+    }
+
+    #[test]
+    fn as_str() {
+        let code = FourCC(*b"moov");
+        assert_eq!(b"moov", code.as_bytes());
+        assert_eq!(Ok("moov"), code.as_str());
+        let code = FourCC(*b"u\xffi\0");
+        let err = code.as_str().unwrap_err();
+        assert_eq!(1, err.valid_up_to());
+    }
+
+    #[test]
+    fn iter() {
+        let code = FourCC(*b"moov");
+        let sum: u32 = code.into_iter().map(|b| b as u32).sum();
+        assert_eq!(
+            (b'm' as u32) + (b'o' as u32) + (b'o' as u32) + (b'v' as u32),
+            sum
+        );
+        assert!(code.iter().eq(b"moov".iter()));
+        let mut bytes = [0u8; 8];
+        for (i, b) in code.into_iter().enumerate() {
+            bytes[i] = b;
+        }
+        for (i, b) in (&code).into_iter().enumerate() {
+            bytes[4 + i] = *b;
+        }
+        assert_eq!(b"moovmoov", &bytes);
+    }
+
+    #[test]
+    fn try_from_iter() {
+        assert_eq!(
+            Ok(FourCC(*b"moov")),
+            FourCC::try_from_iter(FourCC(*b"moov"))
+        );
+        assert_eq!(
+            Err(FourCCFromIterError::TooShort { found: 0 }),
+            FourCC::try_from_iter(core::iter::empty())
+        );
+        assert_eq!(
+            Err(FourCCFromIterError::TooLong),
+            FourCC::try_from_iter(b"moovs".iter().copied())
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_iter_error_display() {
+        let err = FourCC::try_from_iter(b"mo".iter().copied()).unwrap_err();
+        assert_eq!("expected 4 bytes for FourCC, got 2", err.to_string());
+        let err = FourCC::try_from_iter(b"moovs".iter().copied()).unwrap_err();
+        assert_eq!("expected 4 bytes for FourCC, got more", err.to_string());
+    }
+
+    #[test]
+    fn index() {
+        let mut code = FourCC(*b"moov");
+        assert_eq!(b'm', code[0]);
+        assert_eq!(b'o', code[1]);
+        assert_eq!(b'o', code[2]);
+        assert_eq!(b'v', code[3]);
+        code[3] = b'f';
+        assert_eq!(FourCC(*b"moof"), code);
+    }
+
+    #[test]
+    fn bytes() {
+        let code = FourCC(*b"\xa9nam");
+        assert_eq!(*b"\xa9nam", code.bytes());
+        assert_eq!(code.to_array(), code.bytes());
+        assert_eq!(code, FourCC::from_bytes(&code.bytes()));
+        assert_eq!(0xa9, code.byte(0));
+        assert_eq!(b'n', code.byte(1));
+        assert_eq!(b'a', code.byte(2));
+        assert_eq!(b'm', code.byte(3));
+        for i in 0..4 {
+            assert_eq!(code[i], code.byte(i));
+        }
+        const LAST: u8 = FourCC(*b"jp2 ").byte(3);
+        assert_eq!(b' ', LAST);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn byte_out_of_bounds() {
+        let code = FourCC(*b"moov");
+        let _ = code.byte(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn index_out_of_bounds() {
+        let code = FourCC(*b"moov");
+        let _ = code[4];
+    }
+
+    #[test]
+    fn ascii_case() {
+        let code = FourCC(*b"Mp4\xe1");
+        assert_eq!(FourCC(*b"MP4\xe1"), code.to_ascii_uppercase());
+        assert_eq!(FourCC(*b"mp4\xe1"), code.to_ascii_lowercase());
+        assert!(code.eq_ignore_ascii_case(&FourCC(*b"mP4\xe1")));
+        assert!(!code.eq_ignore_ascii_case(&FourCC(*b"mP4\xc1")));
+        assert!(!code.eq_ignore_ascii_case(&FourCC(*b"mp5\xe1")));
+        assert!(FourCC(*b"Moov").eq_ignore_ascii_case(&FourCC(*b"MOOV")));
+        // only letters are folded, not other bytes differing in the same bit
+        assert!(!FourCC(*b"@[^_").eq_ignore_ascii_case(&FourCC(*b"`{~\x7f")));
+        assert_eq!(FourCC(*b"@[^_"), FourCC(*b"@[^_").to_ascii_lowercase());
+        assert_eq!(
+            FourCC(*b"`{~\x7f"),
+            FourCC(*b"`{~\x7f").to_ascii_uppercase()
+        );
+    }
+
+    #[test]
+    fn trim_end() {
+        assert_eq!(b"jp2", FourCC(*b"jp2 ").trim_end());
+        assert_eq!(b"", FourCC(*b"    ").trim_end());
+        assert_eq!(b"moov", FourCC(*b"moov").trim_end());
+        assert_eq!(b" a", FourCC(*b" a  ").trim_end());
+        assert_eq!(b"a\0", FourCC(*b"a\0  ").trim_end());
+    }
+
+    #[test]
+    fn from_str_padded() {
+        assert_eq!(Ok(FourCC(*b"id3 ")), FourCC::from_str_padded("id3"));
+        assert_eq!(Ok(FourCC(*b"wav ")), FourCC::from_str_padded("wav"));
+        assert_eq!(Ok(FourCC(*b"a   ")), FourCC::from_str_padded("a"));
+        assert_eq!(Ok(FourCC(*b"RIFF")), FourCC::from_str_padded("RIFF"));
+        // existing spaces are kept
+        assert_eq!(Ok(FourCC(*b" a  ")), FourCC::from_str_padded(" a"));
+        assert_eq!(b"id3", FourCC::from_str_padded("id3").unwrap().trim_end());
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongLength { found: 5 }),
+            FourCC::from_str_padded("moovs").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongLength { found: 0 }),
+            FourCC::from_str_padded("").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::NonAscii { position: 1 }),
+            FourCC::from_str_padded("a\u{e9}").map_err(|e| e.kind())
+        );
+        // lengths are counted in bytes
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongLength { found: 6 }),
+            FourCC::from_str_padded("\u{e9}\u{e9}\u{e9}").map_err(|e| e.kind())
+        );
+    }
+
+    #[test]
+    fn is_printable() {
+        assert!(FourCC(*b"moov").is_printable());
+        assert!(FourCC(*b"moov").is_ascii());
+        assert!(!FourCC(*b"\x00uid").is_printable());
+        assert!(FourCC(*b"\x00uid").is_ascii());
+        assert!(!FourCC(*b"u\xffi ").is_printable());
+        assert!(!FourCC(*b"u\xffi ").is_ascii());
+        assert!(!FourCC(*b"del\x7f").is_printable());
+        assert!(FourCC(*b"del\x7f").is_ascii());
+        assert!(FourCC(*b"AVC1").is_ascii_alphanumeric());
+        assert!(!FourCC(*b"jp2 ").is_ascii_alphanumeric());
+        assert!(!FourCC(*b"u\xffid").is_ascii_alphanumeric());
+    }
+
+    #[test]
+    fn try_from_slice() {
+        let data = b"moofftyp";
+        assert_eq!(
+            FourCC::try_from_slice(&data[0..0]),
+            Err(FourCCFromSliceError { actual: 0 })
+        );
+        assert_eq!(
+            FourCC::try_from_slice(&data[0..3]),
+            Err(FourCCFromSliceError { actual: 3 })
+        );
+        assert_eq!(FourCC::try_from_slice(&data[0..4]), Ok(FourCC(*b"moof")));
+        assert_eq!(
+            FourCC::try_from_slice(&data[0..5]),
+            Err(FourCCFromSliceError { actual: 5 })
+        );
+        assert_eq!(
+            FourCC::try_from_slice(&data[..]),
+            Err(FourCCFromSliceError { actual: 8 })
+        );
+    }
+
+    #[test]
+    fn from_slice() {
+        let data = b"moofftyp";
+        assert_eq!(FourCC(*b"moof"), FourCC::from(&data[0..4]));
+        // only the first four bytes are used
+        assert_eq!(FourCC(*b"moof"), FourCC::from(&data[0..5]));
+        assert_eq!(FourCC(*b"moof"), FourCC::from(&data[..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_short_slice() {
+        let _ = FourCC::from(&b"moo"[..]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_empty_slice() {
+        let _ = FourCC::from(&b""[..]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_from_vec() {
+        assert_eq!(FourCC::try_from(b"moof".to_vec()), Ok(FourCC(*b"moof")));
+        assert_eq!(FourCC::try_from(&b"moof".to_vec()), Ok(FourCC(*b"moof")));
+        let err = FourCC::try_from(b"moo".to_vec()).unwrap_err();
+        assert_eq!(3, err.actual_len());
+        #[cfg(feature = "std")]
+        assert_eq!("expected 4 bytes for FourCC, got 3", err.to_string());
+    }
+
+    #[test]
+    fn array_conversions() {
+        let code = FourCC::from([b'm', b'o', b'o', b'v']);
+        assert_eq!(FourCC(*b"moov"), code);
+        let array: [u8; 4] = code.into();
+        assert_eq!(*b"moov", array);
+
+        // owned and borrowed arrays are both accepted where a FourCC is expected
+        fn accept(code: impl Into<FourCC>) -> FourCC {
+            code.into()
+        }
+        let computed = [b'm', b'o', b'o', b'v'].map(|b| b.to_ascii_uppercase());
+        assert_eq!(FourCC(*b"MOOV"), accept(computed));
+        assert_eq!(FourCC(*b"MOOV"), accept(b"MOOV"));
+
+        for bytes in [*b"moov", *b"\0\0\0\0", *b"\xff\xfe\x01\x80"] {
+            let round_trip: [u8; 4] = FourCC::from(bytes).into();
+            assert_eq!(bytes, round_trip);
+            let round_trip: [u8; 4] = FourCC::from(&bytes).into();
+            assert_eq!(bytes, round_trip);
+        }
+    }
+
+    #[test]
+    fn int_conversions() {
+        let val: u32 = FourCC(*b"ABCD").into();
+        assert_eq!(0x41424344_u32, val);
+        let code: FourCC = 0x41424344u32.into();
+        assert_eq!(FourCC(*b"ABCD"), code);
+        const VAL: u32 = FourCC(*b"ABCD").to_u32();
+        assert_eq!(0x41424344_u32, VAL);
+        const CODE: FourCC = FourCC::from_u32(0x41424344);
+        assert_eq!(FourCC(*b"ABCD"), CODE);
+    }
+
+    #[test]
+    fn eq_other_types() {
+        let code = FourCC(*b"moof");
+        assert!(code == *b"moof");
+        assert!(*b"moof" == code);
+        assert!(code != *b"moov");
+        assert!(*b"moov" != code);
+        assert!(code == 0x6d6f6f66u32);
+        assert!(0x6d6f6f66u32 == code);
+        assert!(code != 0x666f6f6du32);
+        assert!(0x666f6f6du32 != code);
+        assert_eq!(code, "moof");
+        assert_eq!("moof", code);
+        assert_eq!(code, *"moof");
+        assert_eq!(*"moof", code);
+        assert_ne!(code, "moov");
+        assert_ne!(code, "moo");
+        assert_ne!(code, "moofs");
+        assert_ne!(code, "");
+        assert_ne!(FourCC(*b"\xc3\xa9ab"), "\u{e9}a");
+        assert_eq!(FourCC(*b"\xc3\xa9ab"), "\u{e9}ab");
+    }
+
+    #[test]
+    fn consts() {
+        assert_eq!(FourCC::ZERO, FourCC::MIN);
+        assert!(FourCC::ZERO.is_zero());
+        assert!(!FourCC::MAX.is_zero());
+        assert_eq!(0u32, FourCC::ZERO);
+        assert_eq!(u32::MAX, FourCC::MAX);
+        for code in [
+            FourCC(*b"moov"),
+            FourCC(*b"\0\0\0\x01"),
+            FourCC(*b"\xff\xff\xff\xfe"),
+        ] {
+            assert!(FourCC::MIN < code);
+            assert!(code < FourCC::MAX);
+        }
+    }
+
+    #[test]
+    fn bit_ops() {
+        // combine a category in the first byte with an index in the remaining three
+        let category = FourCC(*b"c\0\0\0");
+        let code = category | 0x00_01_02_03;
+        assert_eq!(FourCC(*b"c\x01\x02\x03"), code);
+        assert_eq!(category, code & FourCC(*b"\xff\0\0\0"));
+        assert_eq!(FourCC(*b"\0\x01\x02\x03"), code & 0x00_ff_ff_ff);
+        assert_eq!(category, code ^ 0x00_01_02_03);
+        assert_eq!(FourCC::ZERO, code ^ code);
+        assert_eq!(code, code | code);
+        // ASCII case is bit 5 of each letter
+        assert_eq!(FourCC(*b"MOOV"), FourCC(*b"moov") ^ FourCC(*b"    "));
+        assert_eq!(FourCC::MAX, !FourCC::ZERO);
+        assert_eq!(FourCC(*b"\x92\x90\x90\x89"), !FourCC(*b"moov"));
+
+        let mut code = FourCC(*b"moov");
+        code &= 0xdf_df_df_df;
+        assert_eq!(FourCC(*b"MOOV"), code);
+        code |= FourCC(*b"    ");
+        assert_eq!(FourCC(*b"moov"), code);
+        code ^= 0x00_00_00_01;
+        assert_eq!(FourCC(*b"moow"), code);
+        code ^= FourCC(*b"\0\0\0\x01");
+        assert_eq!(FourCC(*b"moov"), code);
+        code &= FourCC(*b"\xff\xff\0\0");
+        assert_eq!(FourCC(*b"mo\0\0"), code);
+        code |= 0x00_00_6f_76;
+        assert_eq!(FourCC(*b"moov"), code);
+    }
+
+    #[test]
+    fn reverse_bytes() {
+        const REVERSED: FourCC = FourCC(*b"moov").reverse_bytes();
+        assert_eq!(FourCC(*b"voom"), REVERSED);
+        assert_eq!(FourCC(*b"moov"), REVERSED.reverse_bytes());
+        let code = FourCC(*b"\x01\x02\x03\x04");
+        assert_eq!(code.to_u32_le(), u32::from(code.reverse_bytes()));
+        assert_eq!(
+            code.reverse_bytes(),
+            FourCC::from(u32::from(code).swap_bytes())
+        );
+        const SWAPPED: FourCC = FourCC(*b"moov").swap_bytes();
+        assert_eq!(REVERSED, SWAPPED);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let code = FourCC(*b"ab\xff\xff");
+        assert_eq!(FourCC(*b"ac\0\0"), code.wrapping_add(1));
+        assert_eq!(Some(FourCC(*b"ac\0\0")), code.checked_add(1));
+        assert_eq!(FourCC(*b"ac\0\0"), code.saturating_add(1));
+        assert_eq!(code, FourCC(*b"ac\0\0").wrapping_sub(1));
+        assert_eq!(Some(code), FourCC(*b"ac\0\0").checked_sub(1));
+        assert_eq!(code, FourCC(*b"ac\0\0").saturating_sub(1));
+
+        assert_eq!(FourCC(*b"\0\0\0\x01"), FourCC::MAX.wrapping_add(2));
+        assert_eq!(None, FourCC::MAX.checked_add(2));
+        assert_eq!(
+            FourCC::MAX,
+            FourCC(*b"\xff\xff\xff\0").saturating_add(0x1000)
+        );
+        assert_eq!(FourCC(*b"\xff\xff\xff\xfe"), FourCC::MIN.wrapping_sub(2));
+        assert_eq!(None, FourCC(*b"\0\0\0\x01").checked_sub(2));
+        assert_eq!(FourCC::MIN, FourCC(*b"\0\0\0\x01").saturating_sub(2));
+
+        // enumerate a range of codes
+        let mut count = 0;
+        let mut code = FourCC(*b"ab00");
+        while code <= FourCC(*b"ab09") {
+            count += 1;
+            code = code.wrapping_add(1);
+        }
+        assert_eq!(10, count);
+    }
 
     #[test]
-    fn eq() {
-        assert_eq!(FourCC(*b"uuid"), b"uuid".into());
-        assert_ne!(FourCC(*b"uuid"), b"diuu".into());
+    fn default() {
+        #[derive(Default)]
+        struct BoxHeader {
+            size: u32,
+            box_type: FourCC,
+        }
+        let header = BoxHeader::default();
+        assert_eq!(0, header.size);
+        assert_eq!(FourCC(*b"\0\0\0\0"), header.box_type);
+        assert_eq!(FourCC::ZERO, FourCC::default());
+        assert_eq!(u32::default(), FourCC::default());
     }
 
     #[test]
-    fn int_conversions() {
-        let val: u32 = FourCC(*b"ABCD").into();
-        assert_eq!(0x41424344_u32, val);
-        assert_eq!(FourCC(*b"ABCD"), 0x41424344u32.into());
+    fn ord() {
+        assert!(FourCC(*b"\x00uid") < FourCC(*b"\\uid"));
+        assert!(FourCC(*b"uuid") < FourCC(*b"uui\xff"));
+        assert!(FourCC(*b"\x7fuid") < FourCC(*b"\x80uid"));
+        let mut codes = [
+            FourCC(*b"\xffabc"),
+            FourCC(*b"abcd"),
+            FourCC(*b"\x00bcd"),
+            FourCC(*b"ABCD"),
+        ];
+        codes.sort();
+        assert_eq!(
+            codes,
+            [
+                FourCC(*b"\x00bcd"),
+                FourCC(*b"ABCD"),
+                FourCC(*b"abcd"),
+                FourCC(*b"\xffabc"),
+            ]
+        );
+        for w in codes.windows(2) {
+            assert!(u32::from(w[0]) < u32::from(w[1]));
+        }
+    }
+
+    #[test]
+    fn ord_consistent_with_eq_and_u32() {
+        let codes = [
+            FourCC(*b"\0\0\0\0"),
+            FourCC(*b"\0\0\0\x01"),
+            FourCC(*b"\\x00"),
+            FourCC(*b"moof"),
+            FourCC(*b"moov"),
+            FourCC(*b"\x80\0\0\0"),
+            FourCC(*b"\xff\xff\xff\xff"),
+        ];
+        for a in &codes {
+            for b in &codes {
+                assert_eq!(a.cmp(b), u32::from(*a).cmp(&u32::from(*b)));
+                assert_eq!(a.cmp(b) == Ordering::Equal, a == b);
+                assert_eq!(a.partial_cmp(b), Some(a.cmp(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn le_int_conversions() {
+        assert_eq!(0x44434241, FourCC(*b"ABCD").to_u32_le());
+        assert_eq!(FourCC(*b"ABCD"), FourCC::from_u32_le(0x44434241));
+        for &val in &[
+            0,
+            1,
+            0x41424344,
+            0x7fffffff,
+            0x80000000,
+            0xdeadbeef,
+            u32::MAX,
+        ] {
+            assert_eq!(val, FourCC::from_u32_le(val).to_u32_le());
+            assert_eq!(val.swap_bytes(), u32::from(FourCC::from_u32_le(val)));
+        }
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(Ok(FourCC(*b"moov")), "moov".parse());
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongLength { found: 0 }),
+            "".parse::<FourCC>().map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongLength { found: 5 }),
+            "moovs".parse::<FourCC>().map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::NonAscii { position: 1 }),
+            "aéb".parse::<FourCC>().map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongLength { found: 5 }),
+            "©nam".parse::<FourCC>().map_err(|e| e.kind())
+        );
+    }
+
+    #[test]
+    fn try_from_str() {
+        assert_eq!(Ok(FourCC(*b"moov")), FourCC::try_from("moov"));
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongLength { found: 3 }),
+            FourCC::try_from("mov").map_err(|e| e.kind())
+        );
+        // four bytes of UTF-8, but not four ASCII characters
+        assert_eq!(
+            Err(FourCCParseErrorKind::NonAscii { position: 2 }),
+            FourCC::try_from("moé").map_err(|e| e.kind())
+        );
+        fn convert<T: TryFrom<&'static str>>(s: &'static str) -> Option<T> {
+            T::try_from(s).ok()
+        }
+        assert_eq!(Some(FourCC(*b"trak")), convert("trak"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_error_display() {
+        let err = "moo".parse::<FourCC>().unwrap_err();
+        assert_eq!(
+            "expected 4 bytes for FourCC, found 3 in \"moo\"",
+            err.to_string()
+        );
+        let err = "aéb".parse::<FourCC>().unwrap_err();
+        assert_eq!(
+            "non-ASCII character in FourCC at byte 1 in \"aéb\"",
+            err.to_string()
+        );
+        // control characters in the input are escaped
+        let err = "mo\nov".parse::<FourCC>().unwrap_err();
+        assert_eq!(
+            "expected 4 bytes for FourCC, found 5 in \"mo\\nov\"",
+            err.to_string()
+        );
+        // long input is truncated, at a character boundary
+        let long = "0123456789012345678901234567890é";
+        let err = long.parse::<FourCC>().unwrap_err();
+        assert_eq!(&long[..31], err.input());
+        assert_eq!(
+            format!(
+                "expected 4 bytes for FourCC, found 33 in {:?}...",
+                &long[..31]
+            ),
+            err.to_string()
+        );
+        assert_eq!(
+            "expected 4 bytes for FourCC, found 33",
+            err.kind().to_string()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_error_propagates() {
+        fn brand(config: &str) -> Result<FourCC, Box<dyn std::error::Error>> {
+            Ok(config.trim().parse()?)
+        }
+        assert_eq!(FourCC(*b"isom"), brand(" isom\n").unwrap());
+        let err = brand("iso").unwrap_err();
+        assert!(err.downcast_ref::<FourCCParseError>().is_some());
+    }
+
+    #[test]
+    fn ne_int_conversions() {
+        let code = FourCC(*b"ABCD");
+        #[cfg(target_endian = "little")]
+        assert_eq!(code.to_u32_le(), code.to_u32_ne());
+        #[cfg(target_endian = "big")]
+        assert_eq!(u32::from(code), code.to_u32_ne());
+        for &val in &[0, 0x41424344, 0x80000000, u32::MAX] {
+            assert_eq!(val, FourCC::from_u32_ne(val).to_u32_ne());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hex() {
+        assert_eq!("6d6f6f66", format!("{:x}", FourCC(*b"moof")));
+        assert_eq!("6D6F6F66", format!("{:X}", FourCC(*b"moof")));
+        assert_eq!("0x6d6f6f66", format!("{:#x}", FourCC(*b"moof")));
+        assert_eq!("0x6D6F6F66", format!("{:#X}", FourCC(*b"moof")));
+        assert_eq!("000000ff", format!("{:x}", FourCC(*b"\0\0\0\xff")));
+        assert_eq!("  6d6f6f66", format!("{:>10x}", FourCC(*b"moof")));
+        assert_eq!("0x006d6f6f66", format!("{:#012x}", FourCC(*b"moof")));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn binary_and_octal() {
+        let moov = FourCC(*b"moov");
+        assert_eq!("01101101011011110110111101110110", format!("{:b}", moov));
+        assert_eq!("0b01101101011011110110111101110110", format!("{:#b}", moov));
+        assert_eq!("15533667566", format!("{:o}", moov));
+        assert_eq!("0o15533667566", format!("{:#o}", moov));
+        for code in [moov, FourCC::ZERO, FourCC::MAX, FourCC(*b"\x80\0\x01\x7f")] {
+            let val = code.to_u32();
+            assert_eq!(format!("{:032b}", val), format!("{:b}", code));
+            assert_eq!(format!("{:011o}", val), format!("{:o}", code));
+        }
+        assert_eq!(
+            "00000000000000000000000000000000",
+            format!("{:b}", FourCC::ZERO)
+        );
+        assert_eq!("37777777777", format!("{:o}", FourCC::MAX));
+        assert_eq!(
+            "00000000000000000000000000000001",
+            format!("{:b}", FourCC(*b"\0\0\0\x01"))
+        );
+        // padding applies after the prefix when zero-padding, and around it otherwise
+        assert_eq!("0o0015533667566", format!("{:#015o}", moov));
+        assert_eq!("  15533667566", format!("{:>13o}", moov));
+        assert_eq!("15533667566**", format!("{:*<13o}", moov));
+        // width smaller than the digits does not truncate
+        assert_eq!("15533667566", format!("{:4o}", moov));
+    }
+
+    #[test]
+    fn hex_string() {
+        let code = FourCC(*b"\0m\xffv");
+        assert_eq!("006dff76", &*code.to_hex_string());
+        assert_eq!(Ok(code), FourCC::from_hex_str("006dff76"));
+        assert_eq!(Ok(code), FourCC::from_hex_str("006DFF76"));
+        assert_eq!(Ok(code), FourCC::from_hex_str_prefixed("0x006dff76"));
+        assert_eq!(Ok(code), FourCC::from_hex_str_prefixed("0X006DFF76"));
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongHexLength { found: 7 }),
+            FourCC::from_hex_str("006dff7").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongHexLength { found: 10 }),
+            FourCC::from_hex_str("0x006dff76").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::InvalidHexDigit { position: 3 }),
+            FourCC::from_hex_str("006gff76").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::InvalidHexDigit { position: 5 }),
+            FourCC::from_hex_str_prefixed("0x006gff76").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::InvalidHexDigit { position: 0 }),
+            FourCC::from_hex_str("+06dff76").map_err(|e| e.kind())
+        );
+        // lengths are counted in bytes, so multibyte characters are never mistaken for digits
+        assert_eq!(
+            Err(FourCCParseErrorKind::InvalidHexDigit { position: 6 }),
+            FourCC::from_hex_str("006dff\u{e9}").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongHexLength { found: 9 }),
+            FourCC::from_hex_str("006dff7\u{e9}").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(FourCCParseErrorKind::WrongHexLength { found: 0 }),
+            FourCC::from_hex_str("").map_err(|e| e.kind())
+        );
+        let mut state = 0x2545f491u32;
+        for _ in 0..1000 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let code = FourCC::from(state);
+            assert_eq!(Ok(code), FourCC::from_hex_str(&code.to_hex_string()));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hex_string_display() {
+        let hex = FourCC(*b"moov").to_hex_string();
+        assert_eq!("6d6f6f76", hex.to_string());
+        assert_eq!("\"6d6f6f76\"", format!("{:?}", hex));
+        assert_eq!("6d6f6f76  ", format!("{:10}", hex));
+        let code = FourCC(*b"\0m\xffv");
+        assert_eq!(Ok(code), FourCC::from_hex_str(&code.hex().to_string()));
+        let err = FourCC::from_hex_str("6d6f").unwrap_err();
+        assert_eq!(
+            "expected 8 hex digits for FourCC, found 4 bytes in \"6d6f\"",
+            err.to_string()
+        );
+        let err = FourCC::from_hex_str_prefixed("0x6d6f").unwrap_err();
+        assert_eq!("0x6d6f", err.input());
     }
 
     #[cfg(feature = "std")]
@@ -295,24 +2532,645 @@ mod tests {
     fn display() {
         assert_eq!("uuid", format!("{}", FourCC(*b"uuid")));
         assert_eq!("\\x00uid", format!("{}", FourCC(*b"\x00uid")));
+        assert_eq!("uuid    ", format!("{:8}", FourCC(*b"uuid")));
+        assert_eq!("uuid    ", format!("{:<8}", FourCC(*b"uuid")));
+        assert_eq!("    uuid", format!("{:>8}", FourCC(*b"uuid")));
+        assert_eq!("**uuid**", format!("{:*^8}", FourCC(*b"uuid")));
+        assert_eq!("uuid", format!("{:2}", FourCC(*b"uuid")));
+        assert_eq!("uu", format!("{:.2}", FourCC(*b"uuid")));
+        // padding counts the escaped characters
+        assert_eq!("-\\x00uid-", format!("{:-^9}", FourCC(*b"\x00uid")));
+        assert_eq!(
+            "\\xff\\xff\\xff\\xff",
+            format!("{:>8}", FourCC(*b"\xff\xff\xff\xff"))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn debug() {
+        assert_eq!("FourCC(uuid)", format!("{:?}", FourCC(*b"uuid")));
+        assert_eq!(
+            "FourCC(u\\xffi\\x00)",
+            format!("{:?}", FourCC(*b"u\xffi\0"))
+        );
+        // each character is escaped just once, in the same way as by `Display`
+        assert_eq!("FourCC(\\\"abc)", format!("{:?}", FourCC(*b"\"abc")));
+        assert_eq!("FourCC(\\\\abc)", format!("{:?}", FourCC(*b"\\abc")));
+        assert_eq!("FourCC(\\tabc)", format!("{:?}", FourCC(*b"\tabc")));
+        assert_eq!(
+            "FourCC(\\'\\r\\n\\x7f)",
+            format!("{:?}", FourCC(*b"'\r\n\x7f"))
+        );
+        for code in [FourCC(*b"\"abc"), FourCC(*b"\\abc"), FourCC(*b"\tabc")] {
+            assert_eq!(format!("FourCC({})", code), format!("{:?}", code));
+        }
+        assert_eq!(
+            "[FourCC(moov), FourCC(\\\\\\\"ab)]",
+            format!("{:?}", [FourCC(*b"moov"), FourCC(*b"\\\"ab")])
+        );
+        assert_eq!("FourCC(\n    moov,\n)", format!("{:#?}", FourCC(*b"moov")));
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck() {
+        let data = *b"moovtrakmdia";
+        let codes: &[FourCC] = bytemuck::cast_slice(&data[..]);
+        assert_eq!(
+            &[FourCC(*b"moov"), FourCC(*b"trak"), FourCC(*b"mdia")],
+            codes
+        );
+        assert_eq!(
+            &FourCC(*b"trak"),
+            bytemuck::from_bytes::<FourCC>(&data[4..8])
+        );
+        assert_eq!(
+            FourCC(*b"ovtr"),
+            bytemuck::pod_read_unaligned::<FourCC>(&data[2..6])
+        );
+        assert_eq!(b"moov", bytemuck::bytes_of(&codes[0]));
+        assert_eq!(
+            FourCC(*b"\0\0\0\0"),
+            <FourCC as bytemuck::Zeroable>::zeroed()
+        );
+        let pair: [FourCC; 2] = bytemuck::cast(*b"ftypisom");
+        assert_eq!([FourCC(*b"ftyp"), FourCC(*b"isom")], pair);
+        assert_eq!(*b"ftypisom", bytemuck::cast::<[FourCC; 2], [u8; 8]>(pair));
+        assert_eq!(
+            *b"moov",
+            bytemuck::cast::<FourCC, [u8; 4]>(FourCC(*b"moov"))
+        );
+        let codes = vec![FourCC(*b"moov"), FourCC(*b"\0\0\0\xff")];
+        assert_eq!(
+            b"moov\0\0\0\xff",
+            bytemuck::cast_slice::<FourCC, u8>(&codes)
+        );
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn fourcc_macro() {
+        const MOOV: FourCC = fourcc!("moov");
+        const COPYRIGHT: FourCC = fourcc!(b"\xa9cpy");
+        assert_eq!(FourCC(*b"moov"), MOOV);
+        assert_eq!(FourCC(*b"\xa9cpy"), COPYRIGHT);
+        assert!(matches!(FourCC(*b"trak"), fourcc!("trak")));
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        assert_eq!((4, Some(4)), FourCC::size_hint(0));
+
+        let data = b"moov\xc0\x00\x5f\x7e\xffres";
+        let mut u = Unstructured::new(data);
+        assert_eq!(FourCC(*b"moov"), FourCC::arbitrary(&mut u).unwrap());
+        assert_eq!(FourCC(*b"\"  ?"), FourCC::arbitrary(&mut u).unwrap());
+        assert_eq!(4, u.len());
+        let printable = FourCC::arbitrary(&mut u).unwrap();
+        assert!(printable.is_printable());
+        assert!(u.is_empty());
+
+        // the same input always produces the same values
+        let mut u = Unstructured::new(data);
+        let again: [FourCC; 3] = u.arbitrary().unwrap();
+        assert_eq!([FourCC(*b"moov"), FourCC(*b"\"  ?"), printable], again);
+
+        // every input in a small corpus yields the code for its first four bytes, unless biased
+        let corpus: [&[u8]; 5] = [
+            b"moov",
+            b"\0\0\0\0",
+            b"\xbf\xff\xff\xff",
+            b"\xa9nam",
+            b"trakmdia",
+        ];
+        for input in corpus {
+            let code = FourCC::arbitrary(&mut Unstructured::new(input)).unwrap();
+            assert_eq!(&input[..4], code.as_bytes());
+        }
+
+        // exhausted input is padded with zeros, rather than failing
+        let mut u = Unstructured::new(b"mp");
+        assert_eq!(FourCC(*b"mp\0\0"), FourCC::arbitrary(&mut u).unwrap());
+        assert_eq!(
+            FourCC(*b"mdat"),
+            FourCC::arbitrary_take_rest(Unstructured::new(b"mdat")).unwrap()
+        );
+
+        // all possible raw byte values produce printable output in the biased case
+        for b in 0..=255 {
+            assert!(FourCC::from_arbitrary_bytes([0xc0, b, b, b]).is_printable());
+        }
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn quickcheck() {
+        use quickcheck::{quickcheck, Arbitrary};
+
+        fn u32_round_trip(code: FourCC) -> bool {
+            code == FourCC::from(u32::from(code))
+        }
+        quickcheck(u32_round_trip as fn(FourCC) -> bool);
+
+        let shrunk: Vec<_> = FourCC(*b"\0\0\0\0").shrink().collect();
+        assert!(shrunk.is_empty());
+        let shrunk: Vec<_> = FourCC(*b"aaaa").shrink().collect();
+        assert!(shrunk.is_empty());
+        let shrunk: Vec<_> = FourCC(*b"aaab").shrink().collect();
+        assert_eq!(vec![FourCC(*b"aaaa")], shrunk);
+        let shrunk: Vec<_> = FourCC(*b"\0\0\0\x02").shrink().collect();
+        assert!(shrunk.contains(&FourCC(*b"\0\0\0\0")));
+        let shrunk: Vec<_> = FourCC(*b"moov").shrink().collect();
+        assert!(shrunk.iter().all(|c| c.iter().all(u8::is_ascii_lowercase)));
+        assert!(shrunk.iter().all(|c| *c < FourCC(*b"moov")));
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn rkyv() {
+        #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Debug, PartialEq)]
+        struct FileType {
+            major_brand: FourCC,
+            minor_version: u32,
+            compatible_brands: [FourCC; 2],
+            handler: Option<FourCC>,
+        }
+
+        // the archived form is the FourCC itself, so no conversion is needed on access
+        fn archived_is_fourcc(code: &<FourCC as rkyv::Archive>::Archived) -> &FourCC {
+            code
+        }
+
+        let ftyp = FileType {
+            major_brand: FourCC(*b"isom"),
+            minor_version: 512,
+            compatible_brands: [FourCC(*b"iso2"), FourCC(*b"\0\xff\n ")],
+            handler: Some(FourCC(*b"vide")),
+        };
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&ftyp).unwrap();
+        let archived = rkyv::access::<ArchivedFileType, rkyv::rancor::Error>(&bytes).unwrap();
+        assert_eq!(ftyp.major_brand, *archived_is_fourcc(&archived.major_brand));
+        assert_eq!(ftyp.compatible_brands, archived.compatible_brands);
+        assert_eq!(ftyp.handler, archived.handler.as_ref().copied());
+
+        let deserialized = rkyv::deserialize::<FileType, rkyv::rancor::Error>(archived).unwrap();
+        assert_eq!(ftyp, deserialized);
+
+        // a lone code archives to exactly its own four bytes
+        let code = FourCC(*b"\0mp4");
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&code).unwrap();
+        assert_eq!(b"\0mp4", &bytes[..]);
+        assert_eq!(
+            code,
+            rkyv::from_bytes::<FourCC, rkyv::rancor::Error>(&bytes).unwrap()
+        );
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode() {
+        #[derive(bincode::Encode, bincode::Decode, Debug, PartialEq)]
+        struct BoxHeader {
+            size: u32,
+            box_type: FourCC,
+        }
+
+        let standard = bincode::config::standard();
+        let legacy = bincode::config::legacy();
+
+        // compatible with writers that simply output the four bytes
+        let ftyp = FourCC(*b"ftyp");
+        assert_eq!(
+            b"ftyp",
+            &bincode::encode_to_vec(ftyp, standard).unwrap()[..]
+        );
+        assert_eq!(b"ftyp", &bincode::encode_to_vec(ftyp, legacy).unwrap()[..]);
+
+        let header = BoxHeader {
+            size: 24,
+            box_type: FourCC(*b"\0\0\xffz"),
+        };
+        for data in [
+            bincode::encode_to_vec(&header, standard).unwrap(),
+            bincode::encode_to_vec(&header, legacy.with_big_endian()).unwrap(),
+        ] {
+            assert!(data.ends_with(b"\0\0\xffz"));
+        }
+        let data = bincode::encode_to_vec(&header, standard).unwrap();
+        let (decoded, len) = bincode::decode_from_slice(&data, standard).unwrap();
+        assert_eq!(header, decoded);
+        assert_eq!(data.len(), len);
+        let data = bincode::encode_to_vec(&header, legacy).unwrap();
+        assert_eq!(b"\x18\0\0\0\0\0\xffz", &data[..]);
+        let (decoded, _): (BoxHeader, _) = bincode::decode_from_slice(&data, legacy).unwrap();
+        assert_eq!(header, decoded);
+        let (decoded, _): (FourCC, _) = bincode::borrow_decode_from_slice(b"moov", legacy).unwrap();
+        assert_eq!(FourCC(*b"moov"), decoded);
+
+        assert!(bincode::decode_from_slice::<FourCC, _>(b"moo", standard).is_err());
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn borsh() {
+        for code in [FourCC(*b"moov"), FourCC(*b"\0\0\0\0"), FourCC(*b"a\0b\0")] {
+            let bytes = borsh::to_vec(&code).unwrap();
+            assert_eq!(&code.0[..], &bytes[..]);
+            assert_eq!(code, borsh::from_slice::<FourCC>(&bytes).unwrap());
+        }
+
+        let value = (FourCC(*b"ftyp"), 7u8, FourCC(*b"\0mp4"));
+        let bytes = borsh::to_vec(&value).unwrap();
+        assert_eq!(b"ftyp\x07\0mp4", &bytes[..]);
+        assert_eq!(value, borsh::from_slice(&bytes).unwrap());
+
+        let err = borsh::from_slice::<FourCC>(b"moo").unwrap_err();
+        assert_eq!(borsh::io::ErrorKind::InvalidData, err.kind());
+        let mut reader = &b"mo"[..];
+        assert!(<FourCC as borsh::BorshDeserialize>::deserialize_reader(&mut reader).is_err());
+    }
+
+    #[cfg(all(feature = "borsh", feature = "std"))]
+    #[test]
+    fn borsh_schema() {
+        use borsh::schema::{BorshSchemaContainer, Definition};
+
+        let schema = BorshSchemaContainer::for_type::<FourCC>();
+        assert_eq!("FourCC", schema.declaration());
+        assert_eq!(Ok(4), schema.max_serialized_size());
+        let Some(Definition::Struct { .. }) = schema.get_definition("FourCC") else {
+            panic!("unexpected schema {:?}", schema);
+        };
+    }
+
+    #[cfg(feature = "deku")]
+    #[test]
+    fn deku() {
+        use deku::{DekuContainerRead, DekuContainerWrite, DekuRead, DekuWrite};
+
+        #[derive(DekuRead, DekuWrite, Debug, PartialEq)]
+        #[deku(endian = "big")]
+        struct Aligned {
+            #[deku(bits = 4)]
+            version: u8,
+            #[deku(bits = 4)]
+            flags: u8,
+            kind: FourCC,
+            size: u16,
+        }
+
+        #[derive(DekuRead, DekuWrite, Debug, PartialEq)]
+        #[deku(endian = "little")]
+        struct Unaligned {
+            #[deku(bits = 3)]
+            tag: u8,
+            kind: FourCC,
+            #[deku(bits = 5)]
+            rest: u8,
+        }
+
+        let data = b"\x12moov\x01\x02";
+        let ((rest, _), value) = Aligned::from_bytes((&data[..], 0)).unwrap();
+        assert!(rest.is_empty());
+        let expected = Aligned {
+            version: 1,
+            flags: 2,
+            kind: FourCC(*b"moov"),
+            size: 0x0102,
+        };
+        assert_eq!(expected, value);
+        assert_eq!(&data[..], &value.to_bytes().unwrap()[..]);
+
+        // the code's bytes are not reordered by the little-endian context, and need not start on
+        // a byte boundary
+        let value = Unaligned {
+            tag: 0b101,
+            kind: FourCC(*b"\xa9nam"),
+            rest: 0b10011,
+        };
+        let data = value.to_bytes().unwrap();
+        assert_eq!(5, data.len());
+        assert_eq!(0b1011_0101, data[0]);
+        let ((rest, _), read) = Unaligned::from_bytes((&data[..], 0)).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(value, read);
+
+        let err = Aligned::from_bytes((&b"\x12moo"[..], 0)).unwrap_err();
+        assert!(matches!(err, deku::DekuError::Incomplete(_)), "{:?}", err);
+    }
+
+    #[cfg(feature = "binrw")]
+    #[test]
+    fn binrw() {
+        use binrw::io::Cursor;
+        use binrw::{BinRead, BinReaderExt, BinWrite, BinWriterExt, Endian};
+
+        const MOOV: FourCC = FourCC(*b"moov");
+
+        #[derive(BinRead, BinWrite, Debug, PartialEq)]
+        struct BoxHeader {
+            size: u32,
+            kind: FourCC,
+        }
+
+        #[derive(BinRead, Debug, PartialEq)]
+        struct MovieHeader {
+            size: u32,
+            #[br(assert(kind == MOOV))]
+            kind: FourCC,
+        }
+
+        for (endian, data) in [
+            (Endian::Big, &b"\0\0\0\x08moov"[..]),
+            (Endian::Little, &b"\x08\0\0\0moov"[..]),
+        ] {
+            let header = BoxHeader::read_options(&mut Cursor::new(data), endian, ()).unwrap();
+            assert_eq!(
+                BoxHeader {
+                    size: 8,
+                    kind: MOOV
+                },
+                header
+            );
+            let mut out = Cursor::new(vec![]);
+            header.write_options(&mut out, endian, ()).unwrap();
+            assert_eq!(data, &out.into_inner()[..]);
+
+            let code: FourCC = Cursor::new(b"\0mp4").read_type(endian).unwrap();
+            assert_eq!(FourCC(*b"\0mp4"), code);
+            let mut out = Cursor::new(vec![]);
+            out.write_type(&code, endian).unwrap();
+            assert_eq!(b"\0mp4", &out.into_inner()[..]);
+
+            let header = MovieHeader::read_options(&mut Cursor::new(data), endian, ()).unwrap();
+            assert_eq!(MOOV, header.kind);
+        }
+
+        let err = MovieHeader::read_be(&mut Cursor::new(b"\0\0\0\x08trak")).unwrap_err();
+        assert!(matches!(err, binrw::Error::AssertFail { .. }), "{:?}", err);
+
+        let err = FourCC::read_be(&mut Cursor::new(b"moo")).unwrap_err();
+        assert!(
+            matches!(&err, binrw::Error::Io(e) if e.kind() == binrw::io::ErrorKind::UnexpectedEof),
+            "{:?}",
+            err
+        );
+    }
+
+    #[cfg(feature = "diesel")]
+    #[test]
+    fn diesel() {
+        use diesel::prelude::*;
+
+        diesel::table! {
+            tracks (id) {
+                id -> Integer,
+                codec -> Text,
+            }
+        }
+
+        #[derive(Queryable, Insertable, Debug, PartialEq)]
+        #[diesel(table_name = tracks)]
+        struct Track {
+            id: i32,
+            codec: FourCC,
+        }
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        diesel::sql_query(
+            "CREATE TABLE tracks (id INTEGER PRIMARY KEY NOT NULL, codec TEXT NOT NULL)",
+        )
+        .execute(&mut conn)
+        .unwrap();
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let inserted = vec![
+                Track {
+                    id: 1,
+                    codec: FourCC(*b"avc1"),
+                },
+                Track {
+                    id: 2,
+                    codec: FourCC(*b"mp4a"),
+                },
+            ];
+            diesel::insert_into(tracks::table)
+                .values(&inserted)
+                .execute(conn)?;
+            assert_eq!(
+                inserted,
+                tracks::table.order(tracks::id).load::<Track>(conn)?
+            );
+
+            let id: i32 = tracks::table
+                .filter(tracks::codec.eq(FourCC(*b"mp4a")))
+                .select(tracks::id)
+                .first(conn)?;
+            assert_eq!(2, id);
+            let stored: String = tracks::table
+                .select(diesel::dsl::sql::<diesel::sql_types::Text>("codec"))
+                .filter(tracks::id.eq(1))
+                .first(conn)?;
+            assert_eq!("avc1", stored);
+
+            // codes which are not ASCII can't be stored
+            let err = diesel::insert_into(tracks::table)
+                .values(&Track {
+                    id: 3,
+                    codec: FourCC(*b"\xa9nam"),
+                })
+                .execute(conn)
+                .unwrap_err();
+            assert!(matches!(err, diesel::result::Error::SerializationError(_)));
+
+            // and invalid values can't be loaded
+            diesel::sql_query("INSERT INTO tracks VALUES (4, 'moovs')").execute(conn)?;
+            let err = tracks::table
+                .filter(tracks::id.eq(4))
+                .select(tracks::codec)
+                .first::<FourCC>(conn)
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                diesel::result::Error::DeserializationError(_)
+            ));
+            Ok(())
+        });
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn defmt() {
+        // actually logging needs a global logger, so just check that codes can be logged
+        fn assert_format<T: defmt::Format>(_: &T) {}
+        assert_format(&FourCC(*b"moov"));
+        assert_format(&Some(FourCC(*b"moov")));
+        assert_format(&[FourCC(*b"moov"), FourCC(*b"trak")]);
+    }
+
+    #[cfg(feature = "scroll")]
+    #[test]
+    fn scroll() {
+        use scroll::ctx::SizeWith;
+        use scroll::{Endian, Pread, Pwrite, BE, LE};
+
+        // a sequence of (big-endian size, type) chunk headers, each followed by its payload
+        let data = b"\0\0\0\x0cftypisom\0\0\0\x08free\0\0\0\x0aabcd\0\xff";
+        let mut chunks = vec![];
+        let mut offset = 0;
+        while offset < data.len() {
+            let size: u32 = data.pread_with(offset, BE).unwrap();
+            let kind: FourCC = data.pread_with(offset + 4, BE).unwrap();
+            chunks.push((kind, size));
+            offset += size as usize;
+        }
+        assert_eq!(
+            vec![
+                (FourCC(*b"ftyp"), 12),
+                (FourCC(*b"free"), 8),
+                (FourCC(*b"abcd"), 10),
+            ],
+            chunks
+        );
+        // the code is not affected by the endianness
+        assert_eq!(FourCC(*b"ftyp"), data.pread_with::<FourCC>(4, LE).unwrap());
+        assert_eq!(FourCC(*b"ftyp"), data.pread::<FourCC>(4).unwrap());
+        assert_eq!(4, FourCC::size_with(&Endian::Little));
+
+        let mut buf = [0u8; 6];
+        assert_eq!(4, buf.pwrite_with(FourCC(*b"moov"), 1, LE).unwrap());
+        assert_eq!(b"\0moov\0", &buf);
+        assert_eq!(4, buf.pwrite(FourCC(*b"\xa9nam"), 2).unwrap());
+        assert_eq!(b"\0m\xa9nam", &buf);
+
+        // too little space, or an offset outside the buffer, fail rather than panicking
+        assert!(matches!(
+            data.pread::<FourCC>(data.len() - 2),
+            Err(scroll::Error::TooBig { size: 4, len: 2 })
+        ));
+        assert!(data.pread::<FourCC>(data.len() + 10).is_err());
+        assert!(matches!(
+            buf.pwrite(FourCC(*b"moov"), 3),
+            Err(scroll::Error::TooBig { size: 4, len: 3 })
+        ));
+        assert!(buf.pwrite(FourCC(*b"moov"), 100).is_err());
+    }
+
+    #[cfg(feature = "speedy")]
+    #[test]
+    fn speedy() {
+        use speedy::{Endianness, IsEof, Readable, Writable};
+
+        let code = FourCC(*b"\0ftp");
+        for endianness in [Endianness::LittleEndian, Endianness::BigEndian] {
+            let data = code.write_to_vec_with_ctx(endianness).unwrap();
+            assert_eq!(b"\0ftp", &data[..]);
+            assert_eq!(
+                code,
+                FourCC::read_from_buffer_with_ctx(endianness, &data).unwrap()
+            );
+
+            // only the integer is affected by the endianness
+            let pair = (0x0102_u16, code);
+            let data = pair.write_to_vec_with_ctx(endianness).unwrap();
+            assert_eq!(b"\0ftp", &data[2..]);
+            assert_eq!(
+                pair,
+                <(u16, FourCC)>::read_from_buffer_with_ctx(endianness, &data).unwrap()
+            );
+
+            // truncated input is rejected upfront given a buffer, or as EOF from a stream
+            assert!(FourCC::read_from_buffer_with_ctx(endianness, b"moo").is_err());
+            let err =
+                FourCC::read_from_stream_unbuffered_with_ctx(endianness, &b"moo"[..]).unwrap_err();
+            assert!(err.is_eof());
+        }
+        assert_eq!(
+            4,
+            <FourCC as Readable<'_, Endianness>>::minimum_bytes_needed()
+        );
+        assert_eq!(4, Writable::<Endianness>::bytes_needed(&code).unwrap());
+    }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn zerocopy() {
+        use zerocopy::{AsBytes, FromBytes};
+
+        let code = FourCC::read_from(&b"moov"[..]).unwrap();
+        assert_eq!(FourCC(*b"moov"), code);
+        assert_eq!(b"moov", AsBytes::as_bytes(&code));
+        let mut buf = [0u8; 4];
+        code.write_to(&mut buf[..]).unwrap();
+        assert_eq!(b"moov", &buf);
     }
 
-    #[cfg(feature = "serde")]
+    #[cfg(feature = "zerocopy08")]
     #[test]
-    fn serialize() {
-        use serde_test::{assert_tokens, Token};
+    fn zerocopy08() {
+        use zerocopy08::{FromBytes, Immutable, IntoBytes, KnownLayout, Ref, Unaligned};
+
+        #[derive(FromBytes, IntoBytes, Immutable, KnownLayout, Unaligned)]
+        #[zerocopy(crate = "zerocopy08")]
+        #[repr(C)]
+        struct Header {
+            major_brand: FourCC,
+            minor_version: [u8; 4],
+            compatible_brand: FourCC,
+        }
 
-        let code = FourCC(*b"uuid");
-        assert_tokens(&code, &[Token::Str("uuid")]);
+        // deliberately misaligned
+        let data = b"_isom\0\0\x02\0mp41";
+        let header = Ref::<_, Header>::from_bytes(&data[1..]).unwrap();
+        assert_eq!(FourCC(*b"isom"), header.major_brand);
+        assert_eq!(FourCC(*b"mp41"), header.compatible_brand);
+        assert_eq!(&data[1..], IntoBytes::as_bytes(&*header));
     }
 
-    #[cfg(feature = "serde")]
+    #[cfg(any(feature = "schemars", feature = "schemars1"))]
+    fn check_schema(json: serde_json::Value) {
+        assert_eq!("string", json["type"]);
+        assert_eq!("four-cc", json["format"]);
+        assert_eq!(4, json["minLength"]);
+        assert_eq!(16, json["maxLength"]);
+        assert_eq!("moov", json["examples"][0]);
+        assert!(json["description"].is_string());
+
+        let pattern = json["pattern"].as_str().unwrap();
+        let re = regex::Regex::new(pattern).unwrap();
+        for code in [
+            FourCC(*b"moov"),
+            FourCC(*b"jp2 "),
+            FourCC(*b"\xa9nam"),
+            FourCC(*b"\\'\"\t"),
+            FourCC(*b"\0\r\n\x7f"),
+        ] {
+            assert!(re.is_match(&code.to_string()), "{}", code);
+        }
+        for bad in [
+            "moo", "moovs", "\\q000", "\\x0g00", "\\abc", "'abc", "\"abc",
+        ] {
+            assert!(!re.is_match(bad), "{}", bad);
+        }
+    }
+
+    #[cfg(feature = "schemars1")]
     #[test]
-    fn deserialize() {
-        use std::str::FromStr;
-        let data = "uuid";
-        let code = FourCC::from_str(data).unwrap();
-        assert_eq!(code, FourCC(*b"uuid"));
+    fn schema1() {
+        let schema = schemars1::schema_for!(FourCC);
+        check_schema(schema.to_value());
+
+        #[derive(schemars1::JsonSchema)]
+        #[schemars(crate = "schemars1")]
+        #[allow(dead_code)]
+        struct BoxHeader {
+            kind: FourCC,
+        }
+        let schema = schemars1::schema_for!(BoxHeader).to_value();
+        check_schema(schema["properties"]["kind"].clone());
     }
 
     #[cfg(feature = "schemars")]
@@ -326,5 +3184,7 @@ mod tests {
                 expected_type
             )))
         );
+
+        check_schema(serde_json::to_value(&schema).unwrap());
     }
 }
@@ -0,0 +1,201 @@
+//! [nom](https://docs.rs/nom) parsers for codes stored as four raw bytes.
+//!
+//! The parsers at the top level of this module treat the input as complete, failing if fewer than
+//! four bytes remain, while those in [`streaming`] return `Err::Incomplete` instead.
+//!
+//! ```rust
+//! use four_cc::FourCC;
+//! use nom::{number::complete::be_u32, sequence::tuple, IResult};
+//!
+//! fn box_header(input: &[u8]) -> IResult<&[u8], (u32, FourCC)> {
+//!     tuple((be_u32, four_cc::nom::fourcc))(input)
+//! }
+//!
+//! let (_, (size, box_type)) = box_header(b"\0\0\0\x08free").unwrap();
+//! assert_eq!((8, FourCC(*b"free")), (size, box_type));
+//! ```
+//!
+//! _Requires the `nom` feature._
+
+use crate::FourCC;
+use nom::error::{ErrorKind, ParseError};
+use nom::{Err, IResult};
+
+/// Parses the next four bytes of input as a code.
+///
+/// Fails with `ErrorKind::Eof` if fewer than four bytes remain.
+#[doc(alias = "parse_fourcc")]
+pub fn fourcc<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], FourCC, E> {
+    match input.split_first_chunk() {
+        Some((code, rest)) => Ok((rest, FourCC(*code))),
+        None => Err(Err::Error(E::from_error_kind(input, ErrorKind::Eof))),
+    }
+}
+
+/// Returns a parser which matches the code `expected` in the next four bytes of input.
+///
+/// Fails with `ErrorKind::Tag` if the input holds some other code, or fewer than four bytes
+/// remain.
+///
+/// Codes given as a `u32` can be matched with `tag(FourCC::from_u32(value))`.
+pub fn tag<'a, E: ParseError<&'a [u8]>>(
+    expected: FourCC,
+) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], FourCC, E> {
+    move |input| match input.split_first_chunk() {
+        Some((code, rest)) if *code == expected.0 => Ok((rest, expected)),
+        _ => Err(Err::Error(E::from_error_kind(input, ErrorKind::Tag))),
+    }
+}
+
+/// Parsers for input which may be incomplete, returning `Err::Incomplete` when more than the
+/// available input is needed.
+pub mod streaming {
+    use super::*;
+    use nom::Needed;
+
+    /// Parses the next four bytes of input as a code.
+    ///
+    /// Returns `Err::Incomplete` if fewer than four bytes remain.
+    #[doc(alias = "parse_fourcc")]
+    pub fn fourcc<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], FourCC, E> {
+        match input.split_first_chunk() {
+            Some((code, rest)) => Ok((rest, FourCC(*code))),
+            None => Err(Err::Incomplete(Needed::new(4 - input.len()))),
+        }
+    }
+
+    /// Returns a parser which matches the code `expected` in the next four bytes of input.
+    ///
+    /// Fails with `ErrorKind::Tag` if the input holds some other code.  If fewer than four bytes
+    /// remain but they match the start of `expected`, returns `Err::Incomplete`.
+    pub fn tag<'a, E: ParseError<&'a [u8]>>(
+        expected: FourCC,
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], FourCC, E> {
+        move |input| match input.split_first_chunk() {
+            Some((code, rest)) if *code == expected.0 => Ok((rest, expected)),
+            None if expected.0.starts_with(input) => {
+                Err(Err::Incomplete(Needed::new(4 - input.len())))
+            }
+            _ => Err(Err::Error(E::from_error_kind(input, ErrorKind::Tag))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::error::Error;
+    use nom::number::complete::be_u32;
+    use nom::Needed;
+
+    #[derive(Debug, PartialEq)]
+    struct FileType {
+        major_brand: FourCC,
+        minor_version: u32,
+        compatible_brands: [FourCC; 2],
+    }
+
+    fn ftyp(input: &[u8]) -> IResult<&[u8], FileType> {
+        let (input, _size) = be_u32(input)?;
+        let (input, _) = tag(FourCC(*b"ftyp"))(input)?;
+        let (input, major_brand) = fourcc(input)?;
+        let (input, minor_version) = be_u32(input)?;
+        let (input, first) = fourcc(input)?;
+        let (input, second) = fourcc(input)?;
+        let ftyp = FileType {
+            major_brand,
+            minor_version,
+            compatible_brands: [first, second],
+        };
+        Ok((input, ftyp))
+    }
+
+    const FTYP: &[u8] = b"\0\0\0\x18ftypisom\0\0\x02\0isomiso2";
+
+    #[test]
+    fn ftyp_box() {
+        let expected = FileType {
+            major_brand: FourCC(*b"isom"),
+            minor_version: 512,
+            compatible_brands: [FourCC(*b"isom"), FourCC(*b"iso2")],
+        };
+        assert_eq!(Ok((&b""[..], expected)), ftyp(FTYP));
+        // a different box type
+        let moov = b"\0\0\0\x18moovisom\0\0\x02\0isomiso2";
+        assert_eq!(
+            Err(Err::Error(Error::new(&moov[4..], ErrorKind::Tag))),
+            ftyp(moov)
+        );
+        // truncated in the compatible brands
+        assert_eq!(
+            Err(Err::Error(Error::new(&FTYP[20..22], ErrorKind::Eof))),
+            ftyp(&FTYP[..22])
+        );
+    }
+
+    #[test]
+    fn complete() {
+        assert_eq!(
+            Ok((&b"rest"[..], FourCC(*b"moov"))),
+            fourcc::<Error<_>>(b"moovrest")
+        );
+        assert_eq!(
+            Err(Err::Error(Error::new(&b"moo"[..], ErrorKind::Eof))),
+            fourcc::<Error<_>>(b"moo")
+        );
+        let moov = tag::<Error<_>>(FourCC(*b"moov"));
+        assert_eq!(Ok((&b""[..], FourCC(*b"moov"))), moov(b"moov"));
+        assert_eq!(
+            Err(Err::Error(Error::new(&b"moo"[..], ErrorKind::Tag))),
+            moov(b"moo")
+        );
+        assert_eq!(
+            Err(Err::Error(Error::new(&b"moof"[..], ErrorKind::Tag))),
+            moov(b"moof")
+        );
+    }
+
+    #[test]
+    fn streaming() {
+        assert_eq!(
+            Ok((&b"rest"[..], FourCC(*b"moov"))),
+            streaming::fourcc::<Error<_>>(b"moovrest")
+        );
+        assert_eq!(
+            Err(Err::Incomplete(Needed::new(1))),
+            streaming::fourcc::<Error<_>>(b"moo")
+        );
+        assert_eq!(
+            Err(Err::Incomplete(Needed::new(4))),
+            streaming::fourcc::<Error<_>>(b"")
+        );
+        let moov = streaming::tag::<Error<_>>(FourCC(*b"moov"));
+        assert_eq!(Ok((&b"!"[..], FourCC(*b"moov"))), moov(b"moov!"));
+        assert_eq!(Err(Err::Incomplete(Needed::new(2))), moov(b"mo"));
+        assert_eq!(
+            Err(Err::Error(Error::new(&b"mf"[..], ErrorKind::Tag))),
+            moov(b"mf")
+        );
+        assert_eq!(
+            Err(Err::Error(Error::new(&b"moof"[..], ErrorKind::Tag))),
+            moov(b"moof")
+        );
+    }
+
+    #[test]
+    fn other_error_types() {
+        // the unit error type discards all details
+        assert_eq!(Err(Err::Error(())), fourcc::<()>(b"moo"));
+        assert_eq!(Err(Err::Error(())), tag::<()>(FourCC(*b"moov"))(b"moof"));
+        let free = tag::<(&[u8], ErrorKind)>(FourCC::from_u32(0x66726565));
+        assert_eq!(Ok((&b""[..], FourCC(*b"free"))), free(b"free"));
+        assert_eq!(
+            Err(Err::Error((&b"skip"[..], ErrorKind::Tag))),
+            free(b"skip")
+        );
+        assert_eq!(
+            Err(Err::Incomplete(Needed::new(3))),
+            streaming::fourcc::<()>(b"m")
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! [proptest](https://docs.rs/proptest) support.
+//!
+//! `FourCC` implements [`Arbitrary`], so `any::<FourCC>()` generates codes from all 2^32 byte
+//! patterns, shrinking towards `FourCC(*b"\0\0\0\0")`.  The functions in this module provide
+//! strategies producing more realistic values,
+//!
+//! ```rust
+//! use four_cc::FourCC;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(code in four_cc::proptest::alpha_lower())| {
+//!     prop_assert_eq!(code, code.to_ascii_lowercase());
+//! });
+//! ```
+//!
+//! _Requires the `proptest` feature._
+
+use crate::FourCC;
+use proptest::arbitrary::{any, Arbitrary};
+use proptest::array::{uniform4, UniformArrayStrategy};
+use proptest::strategy::{Map, Strategy};
+
+// The `fourcc_strategy` search alias is on `any_fourcc()`, since doc aliases can't be attached to
+// an impl block
+impl Arbitrary for FourCC {
+    type Parameters = ();
+    type Strategy =
+        Map<UniformArrayStrategy<<u8 as Arbitrary>::Strategy, [u8; 4]>, fn([u8; 4]) -> FourCC>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        uniform4(any::<u8>()).prop_map(FourCC)
+    }
+}
+
+/// Generates codes from all 2^32 byte patterns, the same as `any::<FourCC>()`.
+#[doc(alias = "fourcc_strategy")]
+pub fn any_fourcc() -> impl Strategy<Value = FourCC> {
+    any::<FourCC>()
+}
+
+/// Generates codes made up of printable ASCII bytes, the same as [`printable()`].
+pub fn any_printable_fourcc() -> impl Strategy<Value = FourCC> {
+    printable()
+}
+
+/// Generates codes made up of printable ASCII bytes (`0x20..=0x7e`), shrinking towards
+/// `FourCC(*b"    ")`.
+#[doc(alias = "printable_ascii_fourcc_strategy")]
+pub fn printable() -> impl Strategy<Value = FourCC> {
+    uniform4(0x20u8..=0x7e).prop_map(FourCC)
+}
+
+/// Generates codes made up of lowercase ASCII letters, like many ISO base media file format box
+/// names, shrinking towards `FourCC(*b"aaaa")`.
+pub fn alpha_lower() -> impl Strategy<Value = FourCC> {
+    uniform4(b'a'..=b'z').prop_map(FourCC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    fn shrink_fully<S: Strategy<Value = FourCC>>(strategy: S) -> FourCC {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = strategy.new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        tree.current()
+    }
+
+    #[test]
+    fn shrinking() {
+        assert_eq!(FourCC(*b"\0\0\0\0"), shrink_fully(any::<FourCC>()));
+        assert_eq!(FourCC(*b"    "), shrink_fully(printable()));
+        assert_eq!(FourCC(*b"aaaa"), shrink_fully(alpha_lower()));
+    }
+
+    #[test]
+    fn full_byte_range() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = any::<FourCC>();
+        let mut seen = [[false; 256]; 4];
+        for _ in 0..10_000 {
+            let code = strategy.new_tree(&mut runner).unwrap().current();
+            for (i, b) in code.iter().enumerate() {
+                seen[i][*b as usize] = true;
+            }
+        }
+        // each position independently takes every possible byte value
+        assert!(seen.iter().all(|position| position.iter().all(|&b| b)));
+    }
+
+    proptest! {
+        #[test]
+        fn display_parse_round_trip(code in printable()) {
+            // these characters are escaped by Display, which FromStr does not undo
+            prop_assume!(!code.iter().any(|b| matches!(b, b'\\' | b'\'' | b'"')));
+            prop_assert_eq!(Ok(code), code.to_string().parse());
+        }
+
+        #[test]
+        fn any_printable_values(code in any_printable_fourcc()) {
+            prop_assert!(code.is_printable());
+        }
+
+        #[test]
+        fn alpha_lower_values(code in alpha_lower()) {
+            prop_assert!(code.iter().all(u8::is_ascii_lowercase));
+        }
+
+        #[test]
+        fn u32_round_trip(code in any_fourcc()) {
+            prop_assert_eq!(code, FourCC::from(code.to_u32()));
+        }
+
+        #[test]
+        fn hex_round_trip(code in any::<FourCC>()) {
+            prop_assert_eq!(Ok(code), FourCC::from_hex_str(&code.to_hex_string()));
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! [rand](https://docs.rs/rand) support.
+//!
+//! The [`Standard`] distribution generates codes uniformly from all 2^32 byte patterns, while
+//! [`PrintableFourCC`] generates only printable ASCII codes,
+//!
+//! ```rust
+//! use four_cc::{rand::PrintableFourCC, FourCC};
+//! use rand::{Rng, SeedableRng};
+//! # use rand_chacha::ChaCha8Rng;
+//!
+//! let mut rng = ChaCha8Rng::seed_from_u64(42);
+//! let any_code: FourCC = rng.gen();
+//! let printable = rng.sample(PrintableFourCC);
+//! assert!(printable.is_printable());
+//! ```
+//!
+//! Both work without the `std` feature.
+//!
+//! _Requires the `rand` feature._
+
+use crate::FourCC;
+use rand::distributions::{Distribution, Standard, Uniform};
+use rand::Rng;
+
+impl FourCC {
+    /// Generates a code uniformly from all 2^32 byte patterns, equivalent to `rng.gen::<FourCC>()`.
+    ///
+    /// _Requires the `rand` feature._
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> FourCC {
+        rng.gen()
+    }
+
+    /// Generates a code made up of printable ASCII bytes, equivalent to
+    /// `rng.sample(PrintableFourCC)`.
+    ///
+    /// _Requires the `rand` feature._
+    pub fn random_printable_ascii<R: Rng + ?Sized>(rng: &mut R) -> FourCC {
+        rng.sample(PrintableFourCC)
+    }
+}
+
+impl Distribution<FourCC> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> FourCC {
+        FourCC::from(rng.next_u32())
+    }
+}
+
+/// A distribution generating codes made up of printable ASCII bytes (`0x20..=0x7e`), each chosen
+/// uniformly.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PrintableFourCC;
+
+impl Distribution<FourCC> for PrintableFourCC {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> FourCC {
+        let byte = Uniform::new_inclusive(b' ', b'~');
+        FourCC([
+            byte.sample(rng),
+            byte.sample(rng),
+            byte.sample(rng),
+            byte.sample(rng),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    // The expected values are pinned so that changes to the sampling algorithm, which would alter
+    // the values users see for a given seed, are noticed.
+
+    #[test]
+    fn standard() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let codes: [FourCC; 3] = rng.gen();
+        let expected = [
+            FourCC(*b"\x8c\xa4\r\xb1"),
+            FourCC(*b"g\tL\xea"),
+            FourCC(*b"\xfc\x0e\x8ek"),
+        ];
+        assert_eq!(expected, codes);
+    }
+
+    #[test]
+    fn printable() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let codes: [FourCC; 3] = [
+            rng.sample(PrintableFourCC),
+            rng.sample(PrintableFourCC),
+            rng.sample(PrintableFourCC),
+        ];
+        assert_eq!(
+            [FourCC(*b"TF}'"), FourCC(*b"4XD4"), FourCC(*b"o:vc")],
+            codes
+        );
+        assert!((0..1000).all(|_| rng.sample(PrintableFourCC).is_printable()));
+    }
+
+    #[test]
+    fn inherent() {
+        let mut a = ChaCha8Rng::seed_from_u64(7);
+        let mut b = a.clone();
+        assert_eq!(a.gen::<FourCC>(), FourCC::random(&mut b));
+        assert_eq!(
+            a.sample(PrintableFourCC),
+            FourCC::random_printable_ascii(&mut b)
+        );
+    }
+}
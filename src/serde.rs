@@ -0,0 +1,658 @@
+//! Serde support, and helper modules for alternative serialized representations.
+//!
+//! By default, a `FourCC` is serialized as a string in human-readable formats (escaping any
+//! non-printable bytes in the same way as the `Display` implementation), and as four raw bytes
+//! in binary formats.
+//!
+//! No extra feature is needed for a compact encoding with binary formats like
+//! [postcard](https://docs.rs/postcard), including in `no_std` builds (with
+//! `default-features = false`; serde's own `std` and `alloc` features are then only enabled by
+//! this crate's `std` and `alloc` features),
+//!
+//! ```rust
+//! # use four_cc::FourCC;
+//! let mut buf = [0u8; 8];
+//! let data = postcard::to_slice(&FourCC(*b"moov"), &mut buf).unwrap();
+//! assert_eq!(b"moov", data);
+//! ```
+//!
+//! The four bytes are serialized as a tuple, rather than with `serialize_bytes()`, since in
+//! formats that are not self-describing (like bincode and postcard), a byte string would need a
+//! length prefix.  Self-describing binary formats (like CBOR or MessagePack) may instead encode a
+//! byte string more compactly than a tuple, which can be selected with [`as_bytes`].  Either way,
+//! deserialization accepts both representations.
+
+use crate::{parse_escaped, FourCC};
+use core::fmt;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+impl Serialize for FourCC {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+struct FourCCVisitor;
+
+impl<'de> Visitor<'de> for FourCCVisitor {
+    type Value = FourCC;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a FourCC string, a u32, or four bytes")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        parse_escaped(s).map_err(de::Error::custom)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        FourCC::try_from_slice(v).map_err(|_| de::Error::invalid_length(v.len(), &self))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        u32::try_from(v)
+            .map(FourCC::from)
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        u32::try_from(v)
+            .map(FourCC::from)
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Signed(v), &self))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        visit_byte_seq(seq, &self)
+    }
+}
+
+fn visit_byte_seq<'de, A>(mut seq: A, expected: &dyn de::Expected) -> Result<FourCC, A::Error>
+where
+    A: de::SeqAccess<'de>,
+{
+    let mut buf = [0u8; 4];
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(i, expected))?;
+    }
+    if seq.next_element::<de::IgnoredAny>()?.is_some() {
+        return Err(de::Error::invalid_length(5, expected));
+    }
+    Ok(FourCC(buf))
+}
+
+impl<'de> Deserialize<'de> for FourCC {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(FourCCVisitor)
+        } else {
+            // the visitor also accepts the other representations, in case the data is from a
+            // self-describing format
+            deserializer.deserialize_tuple(4, FourCCVisitor)
+        }
+    }
+}
+
+/// Generates an `opt` submodule handling `Option<FourCC>`, in terms of the `serialize` and
+/// `deserialize` functions of the enclosing module.
+macro_rules! opt_module {
+    ($path:literal) => {
+        #[doc = concat!("Like [`", $path, "`](super), but for `Option<FourCC>` values.")]
+        pub mod opt {
+            use crate::FourCC;
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            struct Wrapper(FourCC);
+
+            impl Serialize for Wrapper {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    super::serialize(&self.0, serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for Wrapper {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    super::deserialize(deserializer).map(Wrapper)
+                }
+            }
+
+            /// Serializes an `Option<FourCC>`.
+            pub fn serialize<S: Serializer>(
+                code: &Option<FourCC>,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                match code {
+                    Some(code) => serializer.serialize_some(&Wrapper(*code)),
+                    None => serializer.serialize_none(),
+                }
+            }
+
+            /// Deserializes an `Option<FourCC>`.
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Option<FourCC>, D::Error> {
+                Option::<Wrapper>::deserialize(deserializer).map(|w| w.map(|w| w.0))
+            }
+        }
+    };
+}
+
+/// Represents a `FourCC` as a big-endian `u32`, in all formats.
+///
+/// ```rust
+/// # use four_cc::FourCC;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Legacy {
+///     #[serde(with = "four_cc::serde::as_u32")]
+///     kind: FourCC,
+///     #[serde(with = "four_cc::serde::as_u32::opt")]
+///     brand: Option<FourCC>,
+/// }
+/// let legacy = Legacy { kind: FourCC(*b"moov"), brand: None };
+/// assert_eq!(
+///     r#"{"kind":1836019574,"brand":null}"#,
+///     serde_json::to_string(&legacy).unwrap()
+/// );
+/// ```
+pub mod as_u32 {
+    use crate::FourCC;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes a `FourCC` as a `u32`.
+    pub fn serialize<S: Serializer>(code: &FourCC, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(u32::from(*code))
+    }
+
+    /// Deserializes a `FourCC` from a `u32`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FourCC, D::Error> {
+        u32::deserialize(deserializer).map(FourCC::from)
+    }
+
+    opt_module!("as_u32");
+}
+
+/// Represents a `FourCC` as a byte string, in all formats.
+///
+/// Formats without native support for byte strings (like JSON) will typically use an array of
+/// four integers.
+pub mod as_bytes {
+    use crate::FourCC;
+    use core::fmt;
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::Serializer;
+
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = FourCC;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("four bytes")
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            FourCC::try_from_slice(v).map_err(|_| de::Error::invalid_length(v.len(), &self))
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+            super::visit_byte_seq(seq, &self)
+        }
+    }
+
+    /// Serializes a `FourCC` as a byte string.
+    pub fn serialize<S: Serializer>(code: &FourCC, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&code.0)
+    }
+
+    /// Deserializes a `FourCC` from a four-byte byte string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FourCC, D::Error> {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+
+    opt_module!("as_bytes");
+}
+
+/// Represents a `FourCC` as a string, in all formats (including binary formats, where the
+/// default would be four raw bytes).
+///
+/// Non-printable bytes are escaped in the same way as the `Display` implementation.
+pub mod as_str {
+    use crate::FourCC;
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes a `FourCC` as an escaped string.
+    pub fn serialize<S: Serializer>(code: &FourCC, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(code)
+    }
+
+    /// Deserializes a `FourCC` from an escaped string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FourCC, D::Error> {
+        deserializer.deserialize_str(super::FourCCVisitor)
+    }
+
+    opt_module!("as_str");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        let code = FourCC(*b"uuid");
+        assert_tokens(&code.readable(), &[Token::Str("uuid")]);
+    }
+
+    #[test]
+    fn deserialize() {
+        let data = "uuid";
+        let code = data.parse::<FourCC>().unwrap();
+        assert_eq!(code, FourCC(*b"uuid"));
+    }
+
+    #[test]
+    fn deserialize_wrong_length() {
+        use serde_test::{assert_de_tokens_error, Readable, Token};
+
+        assert_de_tokens_error::<Readable<FourCC>>(
+            &[Token::Str("uui")],
+            "expected 4 bytes for FourCC, found 3 in \"uui\"",
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn json_round_trip() {
+        let code = FourCC(*b"moov");
+        let json = serde_json::to_string(&code).unwrap();
+        assert_eq!("\"moov\"", json);
+        assert_eq!(code, serde_json::from_str::<FourCC>(&json).unwrap());
+
+        let err = serde_json::from_str::<FourCC>("\"moovextra\"").unwrap_err();
+        assert!(err.to_string().contains("found 9"), "{}", err);
+        let err = serde_json::from_str::<FourCC>("\"mo\"").unwrap_err();
+        assert!(err.to_string().contains("found 2"), "{}", err);
+    }
+
+    #[test]
+    fn deserialize_escaped() {
+        use serde_test::{
+            assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Readable, Token,
+        };
+
+        assert_tokens(
+            &FourCC(*b"u\xffi\0").readable(),
+            &[Token::Str("u\\xffi\\x00")],
+        );
+        assert_tokens(
+            &FourCC(*b"\\'\"\t").readable(),
+            &[Token::Str("\\\\\\'\\\"\\t")],
+        );
+        assert_de_tokens(&FourCC(*b"\xa9nam").readable(), &[Token::Str("\\xA9nam")]);
+        assert_de_tokens_error::<Readable<FourCC>>(
+            &[Token::Str("\\qabc")],
+            "invalid escape sequence in FourCC at byte 0 in \"\\\\qabc\"",
+        );
+        assert_de_tokens_error::<Readable<FourCC>>(
+            &[Token::Str("abc\\x0")],
+            "invalid escape sequence in FourCC at byte 3 in \"abc\\\\x0\"",
+        );
+        assert_de_tokens_error::<Readable<FourCC>>(
+            &[Token::Str("abc\\")],
+            "invalid escape sequence in FourCC at byte 3 in \"abc\\\\\"",
+        );
+        assert_de_tokens_error::<Readable<FourCC>>(
+            &[Token::Str("\\x00\\x00\\x00")],
+            "expected 4 bytes for FourCC, found 3 in \"\\\\x00\\\\x00\\\\x00\"",
+        );
+    }
+
+    #[test]
+    fn serialize_compact() {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        assert_tokens(
+            &FourCC(*b"u\xffi\0").compact(),
+            &[
+                Token::Tuple { len: 4 },
+                Token::U8(b'u'),
+                Token::U8(0xff),
+                Token::U8(b'i'),
+                Token::U8(0),
+                Token::TupleEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn deserialize_compact_alternatives() {
+        use serde_test::{assert_de_tokens, Configure, Token};
+
+        let code = FourCC(*b"moov");
+        assert_de_tokens(&code.compact(), &[Token::Str("moov")]);
+        assert_de_tokens(&code.compact(), &[Token::Bytes(b"moov")]);
+        assert_de_tokens(&code.compact(), &[Token::U32(0x6d6f6f76)]);
+        assert_de_tokens(
+            &code.compact(),
+            &[
+                Token::Seq { len: Some(4) },
+                Token::U8(b'm'),
+                Token::U8(b'o'),
+                Token::U8(b'o'),
+                Token::U8(b'v'),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn postcard_no_alloc() {
+        let mut buf = [0u8; 16];
+        let codes = [FourCC(*b"moov"), FourCC(*b"\0\xff\x7f ")];
+        let data = postcard::to_slice(&codes, &mut buf).unwrap();
+        assert_eq!(b"moov\0\xff\x7f ", data);
+        assert_eq!(codes, postcard::from_bytes::<[FourCC; 2]>(data).unwrap());
+        assert!(postcard::from_bytes::<FourCC>(b"moo").is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn binary_round_trip() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct BoxHeader {
+            size: u32,
+            kind: FourCC,
+        }
+        let header = BoxHeader {
+            size: 8,
+            kind: FourCC(*b"\0ab\0"),
+        };
+
+        let data = bincode1::serialize(&header).unwrap();
+        assert_eq!(&data[..], b"\x08\0\0\0\0ab\0");
+        assert_eq!(header, bincode1::deserialize(&data).unwrap());
+
+        let data = postcard::to_allocvec(&header).unwrap();
+        assert_eq!(&data[..], b"\x08\0ab\0");
+        assert_eq!(header, postcard::from_bytes(&data).unwrap());
+
+        let json = serde_json::to_string(&header).unwrap();
+        assert_eq!(json, r#"{"size":8,"kind":"\\x00ab\\x00"}"#);
+        assert_eq!(header, serde_json::from_str(&json).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn binary_sizes() {
+        #[derive(Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        struct Bytes(#[serde(with = "as_bytes")] FourCC);
+
+        let code = FourCC(*b"moov");
+        // the default tuple needs no length prefix in bincode
+        assert_eq!(b"moov", &bincode1::serialize(&code).unwrap()[..]);
+        assert_eq!(
+            b"\x04\0\0\0\0\0\0\0moov",
+            &bincode1::serialize(&Bytes(code)).unwrap()[..]
+        );
+
+        // while in CBOR, a byte string is smaller than an array of integers
+        let mut tuple = vec![];
+        ciborium::into_writer(&code, &mut tuple).unwrap();
+        assert_eq!(b"\x84\x18m\x18o\x18o\x18v", &tuple[..]);
+        let mut bytes = vec![];
+        ciborium::into_writer(&Bytes(code), &mut bytes).unwrap();
+        assert_eq!(b"\x44moov", &bytes[..]);
+
+        // and both can be read by either
+        assert_eq!(
+            code,
+            ciborium::from_reader::<FourCC, _>(&bytes[..]).unwrap()
+        );
+        assert_eq!(
+            Bytes(code),
+            ciborium::from_reader::<Bytes, _>(&tuple[..]).unwrap()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn json_tolerant() {
+        let expected = FourCC(*b"moov");
+        assert_eq!(
+            expected,
+            serde_json::from_str::<FourCC>("\"moov\"").unwrap()
+        );
+        assert_eq!(
+            expected,
+            serde_json::from_str::<FourCC>("1836019574").unwrap()
+        );
+        assert_eq!(
+            expected,
+            serde_json::from_str::<FourCC>("[109,111,111,118]").unwrap()
+        );
+
+        let err = serde_json::from_str::<FourCC>("4294967296").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid value: integer `4294967296`"));
+        let err = serde_json::from_str::<FourCC>("-1").unwrap_err();
+        assert!(err.to_string().starts_with("invalid value: integer `-1`"));
+        let err = serde_json::from_str::<FourCC>("[109,111,111]").unwrap_err();
+        assert!(err.to_string().starts_with("invalid length 3"));
+        let err = serde_json::from_str::<FourCC>("[109,111,111,118,0]").unwrap_err();
+        assert!(err.to_string().starts_with("invalid length 5"));
+        let err = serde_json::from_str::<FourCC>("[109,111,111,256]").unwrap_err();
+        assert!(err.to_string().starts_with("invalid value: integer `256`"));
+        assert!(serde_json::from_str::<FourCC>("true").is_err());
+    }
+
+    #[test]
+    fn deserialize_bytes() {
+        use serde_test::{assert_de_tokens, assert_de_tokens_error, Configure, Readable, Token};
+
+        assert_de_tokens(&FourCC(*b"moov").readable(), &[Token::Bytes(b"moov")]);
+        assert_de_tokens_error::<Readable<FourCC>>(
+            &[Token::Bytes(b"moo")],
+            "invalid length 3, expected a FourCC string, a u32, or four bytes",
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn json_round_trip_all_bytes() {
+        fn round_trip(code: FourCC) {
+            let json = serde_json::to_string(&code).unwrap();
+            assert_eq!(
+                code,
+                serde_json::from_str::<FourCC>(&json).unwrap(),
+                "{}",
+                json
+            );
+        }
+        for b in 0..=255u8 {
+            round_trip(FourCC([b, b' ', b' ', b' ']));
+            round_trip(FourCC([b'a', b, b'c', b]));
+            round_trip(FourCC([b; 4]));
+        }
+        // xorshift, to cover a spread of other byte patterns deterministically
+        let mut state = 0x2545f491u32;
+        for _ in 0..10_000 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            round_trip(FourCC::from(state));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[derive(Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+    struct Helpers {
+        #[serde(with = "as_u32")]
+        num: FourCC,
+        #[serde(with = "as_bytes")]
+        bytes: FourCC,
+        #[serde(with = "as_str")]
+        string: FourCC,
+        #[serde(with = "as_u32::opt")]
+        opt_num: Option<FourCC>,
+        #[serde(with = "as_bytes::opt")]
+        opt_bytes: Option<FourCC>,
+        #[serde(with = "as_str::opt")]
+        opt_string: Option<FourCC>,
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn helpers_json() {
+        let value = Helpers {
+            num: FourCC(*b"moov"),
+            bytes: FourCC(*b"\0ab\xff"),
+            string: FourCC(*b"jp2 "),
+            opt_num: Some(FourCC(*b"moov")),
+            opt_bytes: None,
+            opt_string: Some(FourCC(*b"\0ab\xff")),
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(
+            json,
+            r#"{"num":1836019574,"bytes":[0,97,98,255],"string":"jp2 ","opt_num":1836019574,"opt_bytes":null,"opt_string":"\\x00ab\\xff"}"#
+        );
+        assert_eq!(value, serde_json::from_str(&json).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn helpers_binary() {
+        let value = Helpers {
+            num: FourCC(*b"moov"),
+            bytes: FourCC(*b"\0ab\xff"),
+            string: FourCC(*b"jp2 "),
+            opt_num: None,
+            opt_bytes: Some(FourCC(*b"trak")),
+            opt_string: None,
+        };
+        let data = postcard::to_allocvec(&value).unwrap();
+        assert!(data.ends_with(b"\x04\0ab\xff\x04jp2 \0\x01\x04trak\0"));
+        assert_eq!(value, postcard::from_bytes(&data).unwrap());
+        let data = bincode1::serialize(&value).unwrap();
+        assert_eq!(value, bincode1::deserialize(&data).unwrap());
+    }
+
+    #[test]
+    fn as_u32_errors() {
+        use serde_test::{assert_de_tokens, assert_de_tokens_error, Token};
+
+        #[derive(Debug, PartialEq, ::serde::Deserialize)]
+        struct Num(#[serde(with = "as_u32")] FourCC);
+        assert_de_tokens(
+            &Num(FourCC(*b"moov")),
+            &[Token::NewtypeStruct { name: "Num" }, Token::U32(0x6d6f6f76)],
+        );
+        assert_de_tokens_error::<Num>(
+            &[Token::NewtypeStruct { name: "Num" }, Token::U64(1 << 32)],
+            "invalid value: integer `4294967296`, expected u32",
+        );
+        assert_de_tokens_error::<Num>(
+            &[Token::NewtypeStruct { name: "Num" }, Token::Str("moov")],
+            "invalid type: string \"moov\", expected u32",
+        );
+    }
+
+    #[test]
+    fn as_bytes_errors() {
+        use serde_test::{assert_de_tokens, assert_de_tokens_error, Token};
+
+        #[derive(Debug, PartialEq, ::serde::Deserialize)]
+        struct Bytes(#[serde(with = "as_bytes")] FourCC);
+        assert_de_tokens(
+            &Bytes(FourCC(*b"moov")),
+            &[
+                Token::NewtypeStruct { name: "Bytes" },
+                Token::Bytes(b"moov"),
+            ],
+        );
+        assert_de_tokens_error::<Bytes>(
+            &[Token::NewtypeStruct { name: "Bytes" }, Token::Bytes(b"moo")],
+            "invalid length 3, expected four bytes",
+        );
+        assert_de_tokens_error::<Bytes>(
+            &[
+                Token::NewtypeStruct { name: "Bytes" },
+                Token::Seq { len: Some(5) },
+                Token::U8(0),
+                Token::U8(0),
+                Token::U8(0),
+                Token::U8(0),
+                Token::U8(0),
+            ],
+            "invalid length 5, expected four bytes",
+        );
+    }
+
+    #[test]
+    fn as_str_errors() {
+        use serde_test::{assert_de_tokens, assert_de_tokens_error, Token};
+
+        #[derive(Debug, PartialEq, ::serde::Deserialize)]
+        struct Str(#[serde(with = "as_str")] FourCC);
+        assert_de_tokens(
+            &Str(FourCC(*b"moov")),
+            &[Token::NewtypeStruct { name: "Str" }, Token::Str("moov")],
+        );
+        assert_de_tokens_error::<Str>(
+            &[Token::NewtypeStruct { name: "Str" }, Token::Str("moo")],
+            "expected 4 bytes for FourCC, found 3 in \"moo\"",
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn map_keys() {
+        use std::collections::HashMap;
+
+        let mut map = HashMap::new();
+        map.insert(FourCC(*b"moov"), 1);
+        map.insert(FourCC(*b"\0ab\xff"), 2);
+        map.insert(FourCC(*b"\\'\"\n"), 3);
+
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(
+            map,
+            serde_json::from_str::<HashMap<FourCC, i32>>(&json).unwrap()
+        );
+
+        let yaml = serde_yaml::to_string(&map).unwrap();
+        assert_eq!(
+            map,
+            serde_yaml::from_str::<HashMap<FourCC, i32>>(&yaml).unwrap()
+        );
+
+        let mut cbor = vec![];
+        ciborium::into_writer(&map, &mut cbor).unwrap();
+        let decoded: HashMap<FourCC, i32> = ciborium::from_reader(&cbor[..]).unwrap();
+        assert_eq!(map, decoded);
+    }
+}
@@ -0,0 +1,222 @@
+//! [sqlx](https://docs.rs/sqlx) support, for binding codes in queries and reading them from
+//! result rows.
+//!
+//! A `FourCC` is stored as text, using the same escaped representation as the `Display`
+//! implementation, and so suits `TEXT` columns in SQLite and `TEXT`, `VARCHAR` or `CHAR(4)`
+//! columns in PostgreSQL (although codes containing bytes which are not printable ASCII need more
+//! than four characters once escaped, and so can't be stored in `CHAR(4)`).  To store the four raw
+//! bytes in a `BLOB` or `BYTEA` column instead, use the [`Bytes`] wrapper.
+//!
+//! The implementations are generic over the database, and so work with any sqlx driver that
+//! supports strings (or byte strings, for [`Bytes`]).
+//!
+//! ```rust
+//! use four_cc::{sqlx::Bytes, FourCC};
+//! use sqlx::{Connection, SqliteConnection};
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+//! sqlx::query("CREATE TABLE tracks (codec TEXT, brand BLOB)")
+//!     .execute(&mut conn)
+//!     .await?;
+//! sqlx::query("INSERT INTO tracks VALUES (?, ?)")
+//!     .bind(FourCC(*b"avc1"))
+//!     .bind(Bytes(FourCC(*b"isom")))
+//!     .execute(&mut conn)
+//!     .await?;
+//! let (codec, Bytes(brand)): (FourCC, Bytes) = sqlx::query_as("SELECT codec, brand FROM tracks")
+//!     .fetch_one(&mut conn)
+//!     .await?;
+//! assert_eq!(FourCC(*b"avc1"), codec);
+//! assert_eq!(FourCC(*b"isom"), brand);
+//! # Ok::<_, sqlx::Error>(())
+//! # }).unwrap();
+//! ```
+//!
+//! _Requires the `sqlx` feature._
+
+use crate::{parse_escaped, FourCC};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type};
+
+impl<DB: Database> Type<DB> for FourCC
+where
+    str: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <str as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <str as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for FourCC
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        self.to_string().encode(buf)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for FourCC
+where
+    &'r str: Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let s = <&str as Decode<DB>>::decode(value)?;
+        Ok(parse_escaped(s)?)
+    }
+}
+
+/// Stores the wrapped code as four raw bytes, for `BLOB` or `BYTEA` columns.
+///
+/// Decoding fails unless the value is exactly four bytes long.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bytes(pub FourCC);
+
+impl From<FourCC> for Bytes {
+    fn from(code: FourCC) -> Self {
+        Bytes(code)
+    }
+}
+
+impl From<Bytes> for FourCC {
+    fn from(bytes: Bytes) -> Self {
+        bytes.0
+    }
+}
+
+impl<DB: Database> Type<DB> for Bytes
+where
+    [u8]: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <[u8] as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <[u8] as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for Bytes
+where
+    Vec<u8>: Encode<'q, DB>,
+{
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        self.0 .0.to_vec().encode(buf)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for Bytes
+where
+    &'r [u8]: Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let bytes = <&[u8] as Decode<DB>>::decode(value)?;
+        Ok(Bytes(FourCC::try_from_slice(bytes)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::{Connection, SqliteConnection};
+
+    async fn connect() -> SqliteConnection {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE codes (id INTEGER, text_code TEXT, blob_code BLOB)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        conn
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let mut conn = connect().await;
+        let codes = [FourCC(*b"moov"), FourCC(*b"\0\0\0\0"), FourCC(*b"\xa9nam")];
+        for (id, code) in codes.iter().enumerate() {
+            sqlx::query("INSERT INTO codes VALUES (?, ?, ?)")
+                .bind(id as i64)
+                .bind(code)
+                .bind(Bytes(*code))
+                .execute(&mut conn)
+                .await
+                .unwrap();
+        }
+        let rows: Vec<(FourCC, Bytes)> =
+            sqlx::query_as("SELECT text_code, blob_code FROM codes ORDER BY id")
+                .fetch_all(&mut conn)
+                .await
+                .unwrap();
+        let expected: Vec<_> = codes.iter().map(|&code| (code, Bytes(code))).collect();
+        assert_eq!(expected, rows);
+
+        // the stored values use the escaped text, and the raw bytes
+        let stored: Vec<(String, String, Vec<u8>)> =
+            sqlx::query_as("SELECT typeof(blob_code), text_code, blob_code FROM codes ORDER BY id")
+                .fetch_all(&mut conn)
+                .await
+                .unwrap();
+        assert_eq!(
+            vec![
+                ("blob".to_string(), "moov".to_string(), b"moov".to_vec()),
+                (
+                    "blob".to_string(),
+                    "\\x00\\x00\\x00\\x00".to_string(),
+                    vec![0; 4]
+                ),
+                (
+                    "blob".to_string(),
+                    "\\xa9nam".to_string(),
+                    b"\xa9nam".to_vec()
+                ),
+            ],
+            stored
+        );
+    }
+
+    #[tokio::test]
+    async fn query_by_code() {
+        let mut conn = connect().await;
+        sqlx::query("INSERT INTO codes VALUES (1, 'moov', NULL), (2, 'trak', NULL)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        let id: i64 = sqlx::query_scalar("SELECT id FROM codes WHERE text_code = ?")
+            .bind(FourCC(*b"trak"))
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(2, id);
+        let code: Option<FourCC> = sqlx::query_scalar("SELECT text_code FROM codes WHERE id = 3")
+            .fetch_optional(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(None, code);
+    }
+
+    #[tokio::test]
+    async fn invalid() {
+        let mut conn = connect().await;
+        sqlx::query("INSERT INTO codes VALUES (1, 'moovs', x'6d6f6f7673')")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        let err = sqlx::query_scalar::<_, FourCC>("SELECT text_code FROM codes")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{:?}", err);
+        let err = sqlx::query_scalar::<_, Bytes>("SELECT blob_code FROM codes")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{:?}", err);
+    }
+}
@@ -0,0 +1,291 @@
+//! Integration with [tokio](https://docs.rs/tokio) and [tokio-util](https://docs.rs/tokio-util).
+//!
+//! [`FourCCCodec`] and [`BoxHeaderCodec`] implement tokio-util's [`Decoder`] and [`Encoder`], for
+//! use with `FramedRead` and `FramedWrite`.
+//!
+//! ```rust
+//! use four_cc::{tokio::FourCCCodec, FourCC};
+//! use futures::StreamExt;
+//! use tokio_util::codec::FramedRead;
+//!
+//! # futures::executor::block_on(async {
+//! let mut codes = FramedRead::new(&b"moovtrak"[..], FourCCCodec);
+//! assert_eq!(FourCC(*b"moov"), codes.next().await.unwrap().unwrap());
+//! assert_eq!(FourCC(*b"trak"), codes.next().await.unwrap().unwrap());
+//! assert!(codes.next().await.is_none());
+//! # });
+//! ```
+//!
+//! The [`AsyncReadFourCCExt`] and [`AsyncWriteFourCCExt`] extension traits add methods for reading
+//! and writing single codes to tokio's `AsyncRead` and `AsyncWrite`, like those of
+//! [`ReadFourCCExt`](crate::io::ReadFourCCExt) for `std::io`,
+//!
+//! ```rust
+//! use four_cc::{tokio::{AsyncReadFourCCExt, AsyncWriteFourCCExt}, FourCC};
+//!
+//! # futures::executor::block_on(async {
+//! let mut data = vec![];
+//! data.write_fourcc(FourCC(*b"moov")).await.unwrap();
+//! assert_eq!(FourCC(*b"moov"), (&data[..]).read_fourcc().await.unwrap());
+//! # });
+//! ```
+//!
+//! _Requires the `tokio` feature._
+
+use crate::bytes::{FourCCBufExt, FourCCBufMutExt};
+use crate::FourCC;
+use bytes::{Buf, BufMut, BytesMut};
+use std::future::Future;
+use std::io;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Extension methods for reading codes from any tokio [`AsyncRead`] implementation.
+pub trait AsyncReadFourCCExt: AsyncRead {
+    /// Reads a code from the next four bytes, like [`AsyncReadExt::read_u32()`].
+    ///
+    /// Fails with [`io::ErrorKind::UnexpectedEof`] if fewer than four bytes are available.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is not cancellation safe.  If the returned future is dropped before
+    /// completing, some of the code's bytes may already have been consumed from the reader, and
+    /// will be lost.
+    fn read_fourcc(&mut self) -> impl Future<Output = io::Result<FourCC>> + '_
+    where
+        Self: Unpin,
+    {
+        async move {
+            let mut buf = [0u8; 4];
+            self.read_exact(&mut buf).await?;
+            Ok(FourCC(buf))
+        }
+    }
+}
+
+impl<R: AsyncRead + ?Sized> AsyncReadFourCCExt for R {}
+
+/// Extension methods for writing codes to any tokio [`AsyncWrite`] implementation.
+pub trait AsyncWriteFourCCExt: AsyncWrite {
+    /// Writes the four bytes of `code`, like [`AsyncWriteExt::write_u32()`].
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is not cancellation safe.  If the returned future is dropped before
+    /// completing, only some of the code's bytes may have been written.
+    fn write_fourcc(&mut self, code: FourCC) -> impl Future<Output = io::Result<()>> + '_
+    where
+        Self: Unpin,
+    {
+        async move { self.write_all(&code.0).await }
+    }
+}
+
+impl<W: AsyncWrite + ?Sized> AsyncWriteFourCCExt for W {}
+
+/// A codec for streams of codes, each encoded as exactly four bytes.
+///
+/// Reaching the end of the stream part way through a code is an error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FourCCCodec;
+
+impl Decoder for FourCCCodec {
+    type Item = FourCC;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<FourCC>, io::Error> {
+        if src.len() < 4 {
+            src.reserve(4 - src.len());
+            return Ok(None);
+        }
+        Ok(Some(src.get_fourcc()))
+    }
+}
+
+impl Encoder<FourCC> for FourCCCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, code: FourCC, dst: &mut BytesMut) -> Result<(), io::Error> {
+        dst.put_fourcc(code);
+        Ok(())
+    }
+}
+
+/// A codec for the eight byte headers used by ISO base media files (and similar formats), made of
+/// a big-endian `u32` size followed by a code giving the type of the box.
+///
+/// Only the header itself is decoded.  Any payload following the header is left in the buffer,
+/// to be consumed by the caller (for example through `FramedRead::read_buffer_mut()`) before
+/// decoding the next header.
+///
+/// ```rust
+/// use bytes::BytesMut;
+/// use four_cc::{tokio::BoxHeaderCodec, FourCC};
+/// use tokio_util::codec::Decoder;
+///
+/// let mut buf = BytesMut::from(&b"\0\0\0\x0cftypisom"[..]);
+/// let header = BoxHeaderCodec.decode(&mut buf).unwrap();
+/// assert_eq!(Some((12, FourCC(*b"ftyp"))), header);
+/// assert_eq!(b"isom", &buf[..]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BoxHeaderCodec;
+
+impl Decoder for BoxHeaderCodec {
+    type Item = (u32, FourCC);
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<(u32, FourCC)>, io::Error> {
+        if src.len() < 8 {
+            src.reserve(8 - src.len());
+            return Ok(None);
+        }
+        let size = src.get_u32();
+        Ok(Some((size, src.get_fourcc())))
+    }
+}
+
+impl Encoder<(u32, FourCC)> for BoxHeaderCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, (size, code): (u32, FourCC), dst: &mut BytesMut) -> io::Result<()> {
+        dst.reserve(8);
+        dst.put_u32(size);
+        dst.put_fourcc(code);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{SinkExt, StreamExt};
+    use std::io::Cursor;
+    use tokio_util::codec::{FramedRead, FramedWrite};
+
+    #[tokio::test]
+    async fn read_write() {
+        let mut data = vec![];
+        data.write_fourcc(FourCC(*b"moov")).await.unwrap();
+        data.write_fourcc(FourCC(*b"\xa9nam")).await.unwrap();
+        assert_eq!(b"moov\xa9nam", &data[..]);
+
+        let mut r = &data[..];
+        assert_eq!(FourCC(*b"moov"), r.read_fourcc().await.unwrap());
+        assert_eq!(FourCC(*b"\xa9nam"), r.read_fourcc().await.unwrap());
+        let err = r.read_fourcc().await.unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+
+        let err = (&b"mo"[..]).read_fourcc().await.unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    #[tokio::test]
+    async fn split_reads() {
+        // a one byte buffer means that the reader has to wait for the writer for each byte
+        let (mut client, mut server) = tokio::io::duplex(1);
+        let write = async move {
+            client.write_fourcc(FourCC(*b"moov")).await.unwrap();
+            client.write_all(b"tr").await.unwrap();
+            tokio::task::yield_now().await;
+            client.write_all(b"ak").await.unwrap();
+            // then end the stream part way through a code
+            client.write_all(b"md").await.unwrap();
+        };
+        let read = async move {
+            let first = server.read_fourcc().await.unwrap();
+            let second = server.read_fourcc().await.unwrap();
+            let err = server.read_fourcc().await.unwrap_err();
+            (first, second, err.kind())
+        };
+        let ((), result) = tokio::join!(write, read);
+        assert_eq!(
+            (
+                FourCC(*b"moov"),
+                FourCC(*b"trak"),
+                io::ErrorKind::UnexpectedEof
+            ),
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn fourcc_framed_read() {
+        let mut codes = FramedRead::new(Cursor::new(b"moov\0\0\0\0\xa9nam"), FourCCCodec);
+        assert_eq!(FourCC(*b"moov"), codes.next().await.unwrap().unwrap());
+        assert_eq!(FourCC::ZERO, codes.next().await.unwrap().unwrap());
+        assert_eq!(FourCC(*b"\xa9nam"), codes.next().await.unwrap().unwrap());
+        assert!(codes.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn fourcc_truncated() {
+        let mut codes = FramedRead::new(Cursor::new(b"moovtr"), FourCCCodec);
+        assert_eq!(FourCC(*b"moov"), codes.next().await.unwrap().unwrap());
+        let err = codes.next().await.unwrap().unwrap_err();
+        assert_eq!(io::ErrorKind::Other, err.kind());
+    }
+
+    #[tokio::test]
+    async fn fourcc_split_reads() {
+        // the codes arrive across several reads, split at awkward points
+        let (client, server) = tokio::io::duplex(3);
+        let write = async move {
+            let mut sink = FramedWrite::new(client, FourCCCodec);
+            for code in [FourCC(*b"moov"), FourCC(*b"trak"), FourCC(*b"mdia")] {
+                sink.send(code).await.unwrap();
+            }
+        };
+        let read = FramedRead::new(server, FourCCCodec).collect::<Vec<_>>();
+        let ((), codes) = tokio::join!(write, read);
+        let codes: Vec<FourCC> = codes.into_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            vec![FourCC(*b"moov"), FourCC(*b"trak"), FourCC(*b"mdia")],
+            codes
+        );
+    }
+
+    #[tokio::test]
+    async fn box_headers() {
+        let data = b"\0\0\0\x0cftypisom\0\0\0\x08free\0\0\0\x09mdat!";
+        let mut boxes = FramedRead::new(Cursor::new(data), BoxHeaderCodec);
+        let mut headers = vec![];
+        while let Some(header) = boxes.next().await {
+            let (size, code) = header.unwrap();
+            headers.push((size, code));
+            // skip over the payload
+            boxes.read_buffer_mut().advance(size as usize - 8);
+        }
+        assert_eq!(
+            vec![
+                (12, FourCC(*b"ftyp")),
+                (8, FourCC(*b"free")),
+                (9, FourCC(*b"mdat")),
+            ],
+            headers
+        );
+    }
+
+    #[test]
+    fn box_header_decode() {
+        let mut buf = BytesMut::from(&b"\0\0\0\x08fr"[..]);
+        assert_eq!(None, BoxHeaderCodec.decode(&mut buf).unwrap());
+        assert_eq!(6, buf.len());
+        buf.extend_from_slice(b"ee");
+        assert_eq!(
+            Some((8, FourCC(*b"free"))),
+            BoxHeaderCodec.decode(&mut buf).unwrap()
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn encode() {
+        let mut buf = BytesMut::new();
+        FourCCCodec.encode(FourCC(*b"moov"), &mut buf).unwrap();
+        BoxHeaderCodec
+            .encode((16, FourCC(*b"mdat")), &mut buf)
+            .unwrap();
+        assert_eq!(b"moov\0\0\0\x10mdat", &buf[..]);
+    }
+}
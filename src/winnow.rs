@@ -0,0 +1,215 @@
+//! [winnow](https://docs.rs/winnow) parsers for codes stored as four raw bytes.
+//!
+//! A `FourCC` value is itself a parser, matching exactly that code,
+//!
+//! ```rust
+//! use four_cc::FourCC;
+//! use winnow::{binary::be_u32, ModalResult, Parser};
+//!
+//! fn free_box(input: &mut &[u8]) -> ModalResult<u32> {
+//!     let size = be_u32.parse_next(input)?;
+//!     FourCC(*b"free").parse_next(input)?;
+//!     Ok(size)
+//! }
+//!
+//! assert_eq!(8, free_box.parse(&b"\0\0\0\x08free"[..]).unwrap());
+//! ```
+//!
+//! All of the parsers support both complete input, and partial input wrapped in
+//! [`winnow::stream::Partial`].
+//!
+//! _Requires the `winnow` feature._
+
+use crate::FourCC;
+use winnow::binary::be_u32;
+use winnow::error::{AddContext, ParserError, StrContext, StrContextValue};
+use winnow::stream::{Compare, Stream, StreamIsPartial};
+use winnow::Parser;
+
+/// Parses the next four bytes of input as a code.
+///
+/// ```rust
+/// # use four_cc::FourCC;
+/// use winnow::{error::ContextError, Parser};
+///
+/// let code = four_cc::winnow::fourcc::<_, ContextError>.parse_peek(&b"moovmdat"[..]);
+/// assert_eq!(Ok((&b"mdat"[..], FourCC(*b"moov"))), code);
+/// ```
+pub fn fourcc<I, E>(input: &mut I) -> Result<FourCC, E>
+where
+    I: StreamIsPartial + Stream<Token = u8>,
+    E: ParserError<I>,
+{
+    be_u32.map(FourCC::from_u32).parse_next(input)
+}
+
+/// Returns a parser matching the code `expected`, which on failure adds error context.
+///
+/// Since the code may have been created at runtime, the context can't quote it without
+/// allocating, and so just describes a "four-character code".  For codes which are known
+/// statically, [`literal_static()`] gives more helpful context.  Note that winnow's
+/// `ContextError` only records context when winnow's `alloc` feature is enabled.
+///
+/// ```rust
+/// # use four_cc::FourCC;
+/// use winnow::{error::ContextError, Parser};
+///
+/// // e.g. the major brand found in an earlier `ftyp` box
+/// let brand = FourCC::from(0x69736f6d);
+/// let err = four_cc::winnow::literal::<_, ContextError>(brand)
+///     .parse(&b"mp41"[..])
+///     .unwrap_err();
+/// assert_eq!("expected four-character code", err.inner().to_string());
+/// ```
+pub fn literal<I, E>(expected: FourCC) -> impl Parser<I, FourCC, E>
+where
+    I: StreamIsPartial + Stream + Compare<[u8; 4]>,
+    E: ParserError<I> + AddContext<I, StrContext>,
+{
+    expected.context(StrContext::Expected(StrContextValue::Description(
+        "four-character code",
+    )))
+}
+
+/// Like [`literal()`], but taking a `'static` reference, which allows the error context to name
+/// the expected code without allocating.
+///
+/// Codes which are not printable ASCII are still described only as a "four-character code".
+///
+/// ```rust
+/// # use four_cc::FourCC;
+/// use winnow::{error::ContextError, Parser};
+///
+/// const MOOV: FourCC = FourCC(*b"moov");
+/// let err = four_cc::winnow::literal_static::<_, ContextError>(&MOOV)
+///     .parse(&b"moof"[..])
+///     .unwrap_err();
+/// assert_eq!("expected `moov`", err.inner().to_string());
+/// ```
+pub fn literal_static<I, E>(expected: &'static FourCC) -> impl Parser<I, FourCC, E>
+where
+    I: StreamIsPartial + Stream + Compare<[u8; 4]>,
+    E: ParserError<I> + AddContext<I, StrContext>,
+{
+    let value = match expected.as_str() {
+        Ok(s) if expected.is_printable() => StrContextValue::StringLiteral(s),
+        _ => StrContextValue::Description("four-character code"),
+    };
+    (*expected).context(StrContext::Expected(value))
+}
+
+/// Matches exactly this code.
+impl<I, E> Parser<I, FourCC, E> for FourCC
+where
+    I: StreamIsPartial + Stream + Compare<[u8; 4]>,
+    E: ParserError<I>,
+{
+    fn parse_next(&mut self, input: &mut I) -> Result<FourCC, E> {
+        winnow::token::literal(self.0)
+            .value(*self)
+            .parse_next(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winnow::error::{ContextError, ErrMode, Needed};
+    use winnow::stream::Partial;
+    use winnow::ModalResult;
+
+    const MOOV: FourCC = FourCC(*b"moov");
+
+    #[test]
+    fn complete() {
+        let mut input = &b"moovrest"[..];
+        assert_eq!(Ok(MOOV), fourcc::<_, ContextError>(&mut input));
+        assert_eq!(b"rest", input);
+        assert!(fourcc::<_, ContextError>.parse_peek(&b"moo"[..]).is_err());
+
+        let mut code = MOOV;
+        let mut input = &b"moovrest"[..];
+        assert_eq!(
+            Ok(MOOV),
+            code.parse_next(&mut input).map_err(|_: ContextError| ())
+        );
+        assert_eq!(b"rest", input);
+        assert!(code
+            .parse_peek(&b"moof"[..])
+            .map_err(|_: ContextError| ())
+            .is_err());
+        assert!(code
+            .parse_peek(&b"moo"[..])
+            .map_err(|_: ContextError| ())
+            .is_err());
+    }
+
+    #[test]
+    fn partial() {
+        fn parse_fourcc(input: &[u8]) -> ModalResult<(Partial<&[u8]>, FourCC)> {
+            fourcc.parse_peek(Partial::new(input))
+        }
+        fn parse_moov(input: &[u8]) -> ModalResult<(Partial<&[u8]>, FourCC)> {
+            let mut code = MOOV;
+            code.parse_peek(Partial::new(input))
+        }
+        assert_eq!(Ok((Partial::new(&b"!"[..]), MOOV)), parse_fourcc(b"moov!"));
+        assert_eq!(
+            Err(ErrMode::Incomplete(Needed::new(1))),
+            parse_fourcc(b"moo")
+        );
+        assert_eq!(Ok((Partial::new(&b"!"[..]), MOOV)), parse_moov(b"moov!"));
+        assert!(matches!(parse_moov(b"mo"), Err(ErrMode::Incomplete(_))));
+        assert!(matches!(parse_moov(b"mf"), Err(ErrMode::Backtrack(_))));
+    }
+
+    #[test]
+    fn literal_context() {
+        static CORRUPT: FourCC = FourCC(*b"\0\xff\0\xff");
+
+        let err = literal_static::<_, ContextError>(&MOOV)
+            .parse(&b"moof"[..])
+            .unwrap_err();
+        assert_eq!(0, err.offset());
+        assert_eq!("expected `moov`", err.inner().to_string());
+
+        let err = literal_static::<_, ContextError>(&CORRUPT)
+            .parse(&b"moov"[..])
+            .unwrap_err();
+        assert_eq!("expected four-character code", err.inner().to_string());
+
+        // context is also added with partial input, once enough input is available to fail
+        let mut input = Partial::new(&b"trak"[..]);
+        let err = literal_static::<_, ErrMode<ContextError>>(&MOOV)
+            .parse_next(&mut input)
+            .unwrap_err();
+        let ErrMode::Backtrack(err) = err else {
+            panic!("unexpected error {:?}", err);
+        };
+        assert_eq!("expected `moov`", err.to_string());
+    }
+
+    #[test]
+    fn literal_by_value() {
+        // a code only known at runtime
+        let brand = FourCC::from_str_padded("qt").unwrap();
+        let mut input = &b"qt  rest"[..];
+        assert_eq!(
+            Ok(brand),
+            literal::<_, ContextError>(brand).parse_next(&mut input)
+        );
+        assert_eq!(b"rest", input);
+
+        let err = literal::<_, ContextError>(brand)
+            .parse(&b"isom"[..])
+            .unwrap_err();
+        assert_eq!(0, err.offset());
+        assert_eq!("expected four-character code", err.inner().to_string());
+
+        let mut input = Partial::new(&b"qt"[..]);
+        assert!(matches!(
+            literal::<_, ErrMode<ContextError>>(brand).parse_next(&mut input),
+            Err(ErrMode::Incomplete(_))
+        ));
+    }
+}